@@ -0,0 +1,843 @@
+//! Derives that must be rejected at compile time.
+
+/// A named variant with several fields needs a `#[from]` marker.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     Query { index: usize, message: String },
+/// }
+/// ```
+struct MultipleNamedFields;
+
+/// The configured aggregate has to be a valid type.
+/// ```compile_fail
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(aggregate = "not a type")]
+/// enum MyError {
+///     OtherError(String),
+/// }
+/// ```
+struct InvalidAggregateType;
+
+/// Without the configured aggregate type among the variants there is nothing to convert into.
+/// ```compile_fail
+/// # mod eyre {
+/// #   pub struct Report;
+/// # }
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(aggregate = "eyre::Report")]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+/// }
+/// ```
+struct MissingAggregateVariant;
+
+/// `without_anyhow` is a flag and does not accept arguments.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[without_anyhow(true)]
+///     CustomError(String),
+/// }
+/// ```
+struct WithoutAnyhowArguments;
+
+/// Misspelled attributes are not silently ignored.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// enum CustomError {
+///     OtherError(anyhow::Error),
+/// }
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[withoutanyhow]
+///     CustomError(CustomError),
+/// }
+/// ```
+struct MisspelledWithoutAnyhow;
+
+/// Two variants wrapping the same type would generate conflicting `From` impls.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[without_anyhow]
+///     Read(std::io::Error),
+///
+///     #[without_anyhow]
+///     Write(std::io::Error),
+/// }
+/// ```
+struct DuplicateInnerTypes;
+
+/// `boxed` needs the field to be a `Box`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[boxed]
+///     #[without_anyhow]
+///     CustomError(String),
+/// }
+/// ```
+struct BoxedWithoutBox;
+
+/// Without an `anyhow::Error` variant there is nothing to convert into.
+/// The error points at the enum's name.
+/// ```compile_fail
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     #[without_anyhow]
+///     CustomError(String),
+/// }
+/// ```
+struct MissingAnyhowVariant;
+
+/// The aggregate already converts from every `std::error::Error`, so `into_aggregate` would conflict.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(Debug, ErrorEnum)]
+/// #[error_enum(std_error, into_aggregate)]
+/// enum MyError {
+///     #[error("other error")]
+///     OtherError(anyhow::Error),
+/// }
+/// ```
+struct IntoAggregateWithStdError;
+
+/// Only one field of a variant can be converted from.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[without_anyhow]
+///     Http(#[from] u16, #[from] String),
+/// }
+/// ```
+struct MultipleFromFields;
+
+/// A tuple variant with several fields needs a `#[from]` marker.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[without_anyhow]
+///     Http(u16, String),
+/// }
+/// ```
+struct MultipleUnnamedFields;
+
+/// Generated method names have to be unique.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(predicates)]
+/// #[allow(non_camel_case_types)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     NotFound,
+///     Not_Found,
+/// }
+/// ```
+struct PredicateCollision;
+
+/// Context is attached in the aggregate, so it can't be combined with `without_anyhow`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// #   impl Error {
+/// #     pub fn new<E>(_: E) -> Self { Error }
+/// #     pub fn context(self, _: &str) -> Self { self }
+/// #   }
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[context("while formatting")]
+///     #[without_anyhow]
+///     Format(std::fmt::Error),
+/// }
+/// ```
+struct ContextWithoutAnyhow;
+
+/// Types listed in `#[from_types]` can't be wrapped by another variant as well.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// #   impl Error {
+/// #     pub fn new<E>(_: E) -> Self { Error }
+/// #   }
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     #[from_types(std::fmt::Error)]
+///     OtherError(anyhow::Error),
+///
+///     #[without_anyhow]
+///     Format(std::fmt::Error),
+/// }
+/// ```
+struct FromTypesCollision;
+
+/// An enum without variants has nothing to convert into.
+/// ```compile_fail
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum Empty {}
+/// ```
+struct EmptyEnum;
+
+/// Tracing conversions needs the `tracing` feature.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(trace)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+/// }
+/// ```
+#[cfg(not(feature = "tracing"))]
+struct TraceWithoutFeature;
+
+/// `#[context]` on an `eyre::Report` aggregate requires the `eyre` feature, which attaches the message with `wrap_err`.
+/// ```compile_fail
+/// # mod eyre {
+/// #   pub struct Report;
+/// #   impl Report {
+/// #       pub fn new<E>(_: E) -> Self { Report }
+/// #       pub fn wrap_err(self, _: &'static str) -> Self { self }
+/// #   }
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(Debug)]
+/// struct PluginError;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(aggregate = "eyre::Report")]
+/// enum MyError {
+///     Report(eyre::Report),
+///     #[context("while loading plugin")]
+///     Plugin(PluginError),
+/// }
+/// ```
+#[cfg(not(feature = "eyre"))]
+struct ContextWithoutEyreFeature;
+
+/// The aggregate variant can't opt out of holding the aggregate.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     #[without_anyhow]
+///     OtherError(anyhow::Error),
+/// }
+/// ```
+struct WithoutAnyhowOnAggregate;
+
+/// Several variants holding the aggregate need one marked with `default_anyhow`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     Internal(anyhow::Error),
+///     Fatal(anyhow::Error),
+/// }
+/// ```
+struct MissingDefaultAnyhow;
+
+/// Only one variant can be marked with `default_anyhow`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     #[default_anyhow]
+///     Internal(anyhow::Error),
+///     #[default_anyhow]
+///     Fatal(anyhow::Error),
+/// }
+/// ```
+struct MultipleDefaultAnyhow;
+
+/// Generating `IntoResponse` needs the `axum` feature.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(into_response)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+/// }
+/// ```
+#[cfg(not(feature = "axum"))]
+struct IntoResponseWithoutFeature;
+
+/// Methods restricted with `vis` can't be called from outside their scope.
+/// ```compile_fail
+/// mod errors {
+///     use error_conversion_macro::ErrorEnum;
+///
+///     #[derive(ErrorEnum)]
+///     #[error_enum(no_aggregate, predicates, vis = "pub(self)")]
+///     pub enum MyError {
+///         NotFound,
+///     }
+/// }
+///
+/// fn main() {
+///     errors::MyError::NotFound.is_not_found();
+/// }
+/// ```
+struct RestrictedVisibility;
+
+/// A reference can't be matched on to extract the aggregate, so it has to be wrapped explicitly.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct IoError;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError<'a> {
+///     OtherError(anyhow::Error),
+///     Io(&'a IoError),
+/// }
+/// ```
+struct ReferenceWithoutAnyhow;
+
+/// A transparent variant displays the inner error, so it can't have its own message.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(Debug)]
+/// struct IoError;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[wrap]
+///     #[transparent]
+///     #[error("io failed")]
+///     Io(IoError),
+/// }
+/// ```
+struct TransparentWithMessage;
+
+/// Every flattened variant needs a variant of the same name to be lifted into.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// enum DatabaseError {
+///     NotFound,
+///     Timeout,
+/// }
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[flatten(NotFound, Timeout)]
+///     Database(DatabaseError),
+///     NotFound,
+/// }
+/// ```
+struct FlattenMissingVariant;
+
+/// A variant converting from the enum itself would conflict with the reflexive `From` impl.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[boxed]
+///     #[without_anyhow]
+///     Nested(Box<MyError>),
+/// }
+/// ```
+struct FromSelf;
+
+/// Nothing is extracted from a `without_anyhow` variant, so there is nothing to map.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// fn redact(error: anyhow::Error) -> anyhow::Error {
+///     error
+/// }
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[without_anyhow]
+///     #[map_anyhow(redact)]
+///     CustomError(String),
+/// }
+/// ```
+struct MapAnyhowWithoutAnyhow;
+
+/// A variant marked with `anyhow` can't compete with another one holding the aggregate.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// type Dynamic = anyhow::Error;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     #[anyhow]
+///     Any(Dynamic),
+///     Other(anyhow::Error),
+/// }
+/// ```
+struct ConflictingAnyhowMarker;
+
+/// Method prefixes have to be valid identifier fragments.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(predicates, predicate_prefix = "is-")]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+/// }
+/// ```
+struct InvalidMethodPrefix;
+
+/// Calling the constructor of a `deprecated_from` variant raises a deprecation warning, denied here.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct LegacyError;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[without_anyhow]
+///     #[deprecated_from("convert into ConfigError instead")]
+///     Legacy(LegacyError),
+/// }
+///
+/// #[deny(deprecated)]
+/// fn load() -> MyError {
+///     MyError::from_legacy(LegacyError)
+/// }
+/// ```
+struct DeprecatedFrom;
+
+/// Of the types in `aggregate_any_of`, the enum may only hold one.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// # mod eyre {
+/// #   pub struct Report;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(aggregate_any_of("anyhow::Error", "eyre::Report"))]
+/// enum MyError {
+///     Anyhow(anyhow::Error),
+///     Eyre(eyre::Report),
+/// }
+/// ```
+struct SeveralAggregatesOfAnyOf;
+
+/// The fields next to `#[from]` are defaulted, so they need a `Default` impl.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct Span;
+/// struct QueryError;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[without_anyhow]
+///     Query { span: Span, #[from] source: QueryError },
+/// }
+/// ```
+struct FieldWithoutDefault;
+
+/// Generating `Serialize` needs the `serde` feature.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(serialize)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+/// }
+/// ```
+#[cfg(not(feature = "serde"))]
+struct SerializeWithoutFeature;
+
+/// Downcasting in `From<anyhow::Error>` conflicts with the `TryFrom` impl of `#[try_from]`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct NotFound;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(downcast_from_anyhow)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[try_from]
+///     NotFound(NotFound),
+/// }
+/// ```
+struct DowncastWithTryFrom;
+
+/// `#[wrap]` and `#[without_anyhow]` disagree on whether the inner type is an error.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct ParseError;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[without_anyhow]
+///     #[wrap]
+///     Parse(ParseError),
+/// }
+/// ```
+struct WrapWithoutAnyhow;
+
+/// `#[from]` selects the field of a variant to convert from, so it can't be placed on a `#[without_anyhow]` variant itself.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct DecodeError;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[without_anyhow]
+///     #[from]
+///     Decode(DecodeError),
+/// }
+/// ```
+struct FromOnWithoutAnyhowVariant;
+
+/// A field is either a `Box` or an `Arc`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct ParseError;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[without_anyhow]
+///     #[boxed]
+///     #[arc]
+///     Parse(Box<ParseError>),
+/// }
+/// ```
+struct BoxedWithArc;
+
+/// The `Kind` enum generated by `error_enum_for` would not be reachable.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::error_enum_for;
+///
+/// enum MyError {
+///     OtherError(anyhow::Error),
+/// }
+///
+/// #[error_enum_for(MyError)]
+/// mod my_error {
+///     #[error_enum(kind)]
+///     enum MyError {
+///         OtherError(anyhow::Error),
+///     }
+/// }
+/// ```
+struct KindForEnumDefinedElsewhere;
+
+/// `#[from_many]` fills a `Vec`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct ValidationError;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[without_anyhow]
+///     #[from_many]
+///     Validation(ValidationError),
+/// }
+/// ```
+struct FromManyWithoutVec;
+
+/// `#[priority]` orders downcasts, which a variant without `#[try_from]` only takes part in with `downcast_from_anyhow`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct Timeout;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[wrap]
+///     #[priority(1)]
+///     Timeout(Timeout),
+/// }
+/// ```
+struct PriorityWithoutDowncast;
+
+/// The kind of aggregate is either `"struct"` or `"enum"`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(aggregate_kind = "union")]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+/// }
+/// ```
+struct InvalidAggregateKind;
+
+/// `assert_converts!` is only exported with the `testing` feature.
+/// ```compile_fail
+/// use error_conversion_macro::assert_converts;
+/// ```
+#[cfg(not(feature = "testing"))]
+struct AssertConvertsWithoutFeature;
+
+/// A type listed in `catch_all_into_anyhow` can't also be converted from by a variant.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct LibError;
+///
+/// #[derive(ErrorEnum)]
+/// #[error_enum(catch_all_into_anyhow(LibError))]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[without_anyhow]
+///     Lib(LibError),
+/// }
+/// ```
+struct CatchAllWithOwnVariant;
+
+/// The aggregate variant holds the aggregate as its single field.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error, String),
+/// }
+/// ```
+struct MultiFieldAggregate;
+
+/// The closure of `#[build_with]` takes the inner value as its single argument.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct Timeout;
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[build_with(|e, retries| MyError::Timeout(e))]
+///     Timeout(Timeout),
+/// }
+/// ```
+struct BuildWithSeveralArguments;
+
+/// Variants of a qualified associated type can't be matched on, so nothing can be extracted from it.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// trait Backend {
+///     type Error;
+/// }
+///
+/// struct Postgres;
+///
+/// enum PostgresError {
+///     Anyhow(anyhow::Error),
+/// }
+///
+/// impl Backend for Postgres {
+///     type Error = PostgresError;
+/// }
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     Postgres(<Postgres as Backend>::Error),
+/// }
+/// ```
+struct QualifiedPathWithoutWrap;
+
+/// `#[redact]` only changes the `Debug` generated by `error_enum(debug)`.
+/// ```compile_fail
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::ErrorEnum;
+///
+/// struct Password(String);
+///
+/// #[derive(ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     #[redact]
+///     #[without_anyhow]
+///     Login(Password),
+/// }
+/// ```
+struct RedactWithoutDebug;
@@ -2,24 +2,190 @@ use proc_macro::TokenStream;
 
 use proc_macro2::Span;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, ToTokens};
-use syn::{Data, DeriveInput, Error, Fields, FieldsUnnamed, Ident, parse_macro_input, Variant};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{Attribute, braced, Data, DeriveInput, Error, Expr, ExprLit, Field, Fields, GenericArgument, Generics, Ident, Lit, LitInt, LitStr, parenthesized, parse_macro_input, parse_quote, Path, PathArguments, Token, Type, TypeParamBound, Variant, Visibility, WherePredicate};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 
+#[cfg(doctest)]
+mod compile_fail;
+
+/// Creates an error pointing at `$span`, or at the whole derive if no span is given.
 macro_rules! derive_error {
-    ($string: tt) => {
-        Error::new(Span::call_site(), $string)
-            .to_compile_error()
-            .into()
+    ($span: expr, $message: expr) => {
+        Error::new($span, $message)
+    };
+    ($message: expr) => {
+        derive_error!(Span::call_site(), $message)
     };
 }
 
 /// Macro for deriving the `From` trait implementation for an enum with error variants.
 /// The macro generates conversions from inner error types to the enum's variants.
+/// Problems with independent variants are all reported at once.
+/// Both tuple variants and named variants with a single field (`Db { source: DbError }`) are supported.
+/// Variants gated with `#[cfg(...)]` get their `From` impls gated the same way. Options matching on every variant,
+/// such as `kind`, `std_error` or the `Display` generated from `#[error]`, don't support them yet and are rejected,
+/// as is `#[try_from]` on a gated variant.
+/// Borrowed inner types such as `ParseError<'input>` take their lifetime from the enum, `enum MyError<'input>`,
+/// whose generics every generated impl carries.
+/// Variants with several fields mark the one to convert from with `#[from]`, the other fields are set to `Default::default()`.
+/// A field whose type has no `Default` impl is reported at the field.
+/// The aggregate variant may hold its error in a `Box`, aggregates are then boxed with `.into()`.
+/// A variant holding a newtype around the aggregate is marked with `deref_anyhow` instead.
+///
+/// `eyre::Report` is used instead of `anyhow::Error` with `error_enum(aggregate = "eyre::Report")`, the default stays `anyhow::Error`.
+/// Extracting an `eyre::Report` works like extracting an `anyhow::Error`, since the aggregate is only moved between variants.
+/// The one difference is `context`, which attaches its message with `wrap_err` and requires the `eyre` feature.
 ///
 /// # Attributes
+/// - `anyhow`: Marks the aggregate variant when its type is written differently, e.g. through `type Dynamic = anyhow::Error`,
+///   which the macro can't resolve. Bypasses the detection by type, so no other variant may hold a literal `anyhow::Error`.
 /// - `without_anyhow`: Skips conversion for variants whose inner type do not have a variant containing an `anyhow::Error`.
+///   Variants holding a reference such as `Io(&'a io::Error)` need it or `wrap`, as nothing can be extracted from a reference.
+///   So do variants holding a qualified associated type such as `Db(<Postgres as Backend>::Error)`, whose variants Rust can't match on.
+///   Variants holding a tuple such as `Rpc((u32, String))` are always wrapped, since a tuple has no variants to extract from.
+/// - `default_anyhow`: Marks the variant `From<anyhow::Error>` converts into when several variants hold an `anyhow::Error`,
+///   such as `Internal(anyhow::Error)` and `Fatal(anyhow::Error)`. The other ones get no conversion and are constructed by hand.
+/// - `deref_anyhow`: Marks the aggregate variant when it holds a newtype such as `struct AppErr(pub anyhow::Error)`.
+///   Aggregates are converted into the newtype with `.into()`, so it has to implement `From<anyhow::Error>`,
+///   and `std_error` and `into_aggregate` reach the inner value through `Deref<Target = anyhow::Error>` and the public field `.0`.
+/// - `wrap`: Wraps the inner type in the variant without extracting an `anyhow::Error` from it, e.g. for inner types that aren't enums.
+///   Unlike `without_anyhow`, the variant is still treated as an error by `std_error` and `into_aggregate`. Can't be combined with `without_anyhow`.
+///   The extraction moves the `anyhow::Error` out of the inner enum, whatever its other variants hold, which Rust forbids
+///   for an inner type implementing `Drop`. Such variants need `wrap` or `without_anyhow`.
+/// - `anyhow_variant = "Unexpected"`: Names the inner type's variant holding the `anyhow::Error` to extract,
+///   for inner enums that don't call it like the aggregate variant of this enum.
+/// - `map_anyhow(redact)`: Passes the `anyhow::Error` extracted from the inner type through the function,
+///   which has to return the field type of the aggregate variant, e.g. to redact or annotate it. Can't be combined with `without_anyhow`.
+/// - `deprecated_from("use Config instead")`: Generates a constructor named after the variant, e.g. `MyError::from_legacy(...)`,
+///   converting like the variant's `From` impl and deprecated with the note. Rust can't deprecate trait impls,
+///   so `From`, `.into()` and `?` don't warn. Moving callers to the constructor gets them warned before the `From` impl is removed.
+/// - `skip_from`: Generates no conversion at all for the variant, e.g. when its inner type already has a hand-written `From` impl.
+///   Takes precedence over `without_anyhow`. On the aggregate variant it skips the `From<anyhow::Error>` impl.
+/// - `try_from`: Instead of `From<Inner>`, the variant is filled by a `TryFrom<anyhow::Error>` impl which downcasts the aggregate.
+///   Variants with `try_from` are tried in declaration order and the aggregate is returned as `Err` if none matches.
+///   The `TryFrom` impl replaces the `From<anyhow::Error>` impl, as the two would conflict.
+/// - `priority(N)`: Changes the order in which `try_from` and `downcast_from_anyhow` downcast into the variant's type,
+///   lower numbers being tried first. Variants without it have priority 0 and are tried in declaration order among each other.
+///   This matters when several types match the same aggregate, such as an `anyhow::Error` with context, which downcasts
+///   into both its context and the error it wraps.
+///   See `error_enum(downcast_from_anyhow)` to downcast in `From<anyhow::Error>` instead.
+/// - `boxed`: For a variant whose field is `Box<Inner>`, converts from `Inner` and boxes it. Works with or without `without_anyhow`.
+/// - `arc`: For a variant whose field is `Arc<Inner>`, converts from `Inner` like `boxed`, and also from an `Arc<Inner>` as is.
+///   Can't be combined with `boxed`.
+/// - `from_many`: For a variant whose field is `Vec<Inner>`, converts from a single `Inner` into a one-element `Vec`,
+///   from a `Vec<Inner>` as is, and implements `FromIterator<Inner>` to collect several. Can't be combined with `boxed` or `arc`.
+/// - `from_types(std::io::Error, std::fmt::Error)`: Also generates a `From` impl for every listed type,
+///   converting it into the variant's field with `.into()`, e.g. to route several error types into one `anyhow::Error` variant.
+///   Variants holding the aggregate wrap the listed types with `anyhow::Error::new`, which keeps their `source()` chain and backtrace.
+/// - `from_display(ParseIntError, Utf8Error)`: For a variant whose field is a `String`, also generates a `From` impl
+///   for every listed type, storing its `Display` message in the field with `to_string()`.
+/// - `from_ref`: Also generates `From<&Inner>`, which clones the inner value and converts it like `From<Inner>`.
+///   The macro can't check that `Inner` is `Clone`, the compiler reports it at the generated `clone` call otherwise.
+/// - `context("...")`: Converts the inner type into the aggregate variant instead, attaching the message with `context` (`wrap_err` for `eyre::Report`, with the `eyre` feature).
+///   Nothing is extracted from the inner type, it is wrapped with `anyhow::Error::new`, so it has to implement `std::error::Error`. Can't be combined with `without_anyhow`.
+/// - `into_anyhow`: Converts the inner type into the aggregate variant with `.into()` instead of matching on it,
+///   for concrete errors such as `std::io::Error` that implement `Into<anyhow::Error>`. Can't be combined with `without_anyhow` or `context`.
+/// - `via(B)`: Converts the inner type into the aggregate variant through `B`, as `B::from(value).into()`,
+///   for types that only reach the aggregate in two steps. Requires `From<Inner> for B` and `Into<anyhow::Error> for B`,
+///   which the compiler checks at the generated impl. Can't be combined with `without_anyhow`, `context` or `into_anyhow`.
+/// - `build_with(|e| MyError::Other(wrap(e)))`: Builds the enum from the inner value with the closure, used as is in the `From` impl
+///   instead of extracting the aggregate or wrapping the value. An escape hatch for conversions the other attributes can't express.
+///   The closure takes the inner value as its single argument and returns the enum.
+/// - `bound("T: Into<anyhow::Error>")`: Adds the where-clause predicates to the `From` impls of the variant only,
+///   for bounds a generic enum's conversion needs that don't belong on the enum itself.
+///   Rust reserves `where` as a keyword, so it can't name the attribute.
+/// - `flatten(NotFound, Timeout)`: For a variant holding another error enum, `From<Inner>` lifts the listed variants
+///   of the inner enum into the variants of the same name of this enum instead of nesting them.
+///   The macro can't see the inner enum, so its variants are listed and have to be shaped like ours.
+///   Inner variants that aren't listed are wrapped in the variant itself. That arm is generated even if every variant is listed,
+///   so a `#[non_exhaustive]` enum of another crate can be flattened too.
+/// - `transparent`: Makes a variant with a single field indistinguishable from the error it holds.
+///   Its `Display` is the inner error's and, with `std_error`, its `source()` is the inner error's `source()`.
+///   Can't be combined with `error("...")`.
+/// - `error("...")`: Generates a `Display` impl using the format string for this variant.
+///   `{0}` or `{}` refers to the field of a tuple variant and `{source}` to the field `source` of a named variant.
+///   Positional placeholders are rejected on named and unit variants, and past the last field of a tuple variant.
+///   Once any variant carries the attribute, the others display their single field or, for unit variants, their name.
+/// - `error_enum(aggregate = "eyre::Report")`: Placed on the enum, sets the aggregate error type used instead of `anyhow::Error`.
+///   The type is matched and emitted exactly as written, so renamed imports such as `aggregate = "ah::Error"` work too,
+///   as does `aggregate = "Error"` for a bare `Error` glob-imported from a prelude.
+///   A boxed trait object such as `aggregate = "Box<dyn std::error::Error + Send + Sync>"` can't be matched on,
+///   so nothing is extracted from inner types and `into_aggregate` builds it with `From`.
+///   Its bounds are kept as written, so `Box<dyn std::error::Error>` and `Box<dyn std::error::Error + Send + Sync>` are
+///   different aggregates, and the latter keeps the enum `Send` and `Sync`. The order of the bounds doesn't matter.
+/// - `error_enum(aggregate_kind = "struct")`: Placed on the enum, declares the configured aggregate a plain struct,
+///   such as a `#[repr(transparent)]` newtype around `anyhow::Error`, which has none of its methods.
+///   Like a boxed trait object, it is never matched on or built with `new` and `msg`: nothing is extracted from inner types
+///   and the aggregate is built with `From`, so it has to convert from errors and strings. Defaults to `"enum"`.
+/// - `error_enum(aggregate_any_of("anyhow::Error", "eyre::Report"))`: Placed on the enum, uses whichever of the types
+///   the enum holds as aggregate, so that crates sharing a configuration can pick their error library.
+///   Holding several of them is an error.
+/// - `error_enum(catch_all_into_anyhow(LibError, OtherError))`: Placed on the enum, generates a `From` impl for every listed type
+///   converting it into the aggregate variant with `.into()`, for errors of other crates that need no variant of their own.
+///   A listed type can't also be converted from by a variant.
+/// - `error_enum(no_aggregate)`: Placed on the enum, drops the requirement for an aggregate variant.
+///   Every variant is then simply wrapped, as there is no aggregate to extract.
+/// - `error_enum(no_anyhow_from)`: Placed on the enum, leaves out the `From<anyhow::Error>` impl, e.g. to write it by hand
+///   and attach context. The aggregate variant is still extracted into by the other conversions, like with `skip_from` on it.
+/// - `error_enum(downcast_from_anyhow)`: Placed on the enum, makes `From<anyhow::Error>` try to downcast the aggregate
+///   into the inner type of every variant in declaration order, converting it with that variant's `From` impl,
+///   and only fall back to the aggregate variant if none matches. This recovers errors erased into the aggregate earlier on.
+///   Variants marked `without_anyhow`, `skip_from`, `context`, `into_anyhow` or `via` are left out, the others' inner types have to
+///   implement `std::error::Error`. Cannot be combined with `try_from` or `no_anyhow_from`.
+/// - `error_enum(into_aggregate)`: Placed on the enum, also generates `From<MyError> for anyhow::Error`.
+///   The aggregate variant returns its inner value, `without_anyhow` variants become a message from their `Display`
+///   and other variants are wrapped with `anyhow::Error::new`. Cannot be combined with `std_error`,
+///   as the aggregate then already converts from the enum.
+/// - `error_enum(into_anyhow_method)`: Placed on the enum, generates an `into_anyhow(self) -> anyhow::Error` method
+///   converting like `into_aggregate`, for enums that would rather not implement a trait of the aggregate's crate.
+///   Unlike `into_aggregate`, it can be combined with `std_error`.
+/// - `error_enum(strict)`: Placed on the enum, requires a `From` impl for every variant other than those holding the aggregate
+///   and those marked `skip_from` or `try_from`, so that unit variants and variants with several fields but no `#[from]`
+///   don't silently break `?`. Every offending variant is reported at once.
+/// - `error_enum(no_std)`: Placed on the enum, makes generated code only reference `core`, and `alloc` for `boxed` and `arc` variants.
+///   `std_error` then implements `core::error::Error`. Cannot be combined with `into_aggregate`.
+/// - `error_enum(predicates)`: Placed on the enum, generates an `is_<variant>(&self) -> bool` method per variant,
+///   e.g. `is_not_found` for `NotFound`.
+/// - `error_enum(accessors)`: Placed on the enum, generates an `as_<variant>(&self) -> Option<&Inner>` method
+///   per variant with a single field.
+/// - `error_enum(predicate_prefix = "variant_is_", accessor_prefix = "variant_as_")`: Placed on the enum,
+///   replaces the `is_` and `as_` prefixes of the methods generated by `predicates` and `accessors`.
+/// - `error_enum(into_inner)`: Placed on the enum, generates an `into_<variant>(self) -> Result<Inner, Self>` method
+///   per variant with a single field, giving back the enum as `Err` if it holds another variant.
+/// - `error_enum(const_ctors)`: Placed on the enum, generates a `const fn <variant>(value: Inner) -> Self` constructor
+///   per variant with a single field, and a `const fn <variant>() -> Self` per unit variant, e.g. `not_found()` for `NotFound`.
+///   Moving the value into the variant drops nothing, so every constructor can be `const`, whatever the field's type.
+///   Variants named after a keyword get a raw constructor name such as `r#type`.
+/// - `error_enum(kind)`: Placed on the enum, generates a fieldless `MyErrorKind` enum with the same variants,
+///   deriving `Debug, Clone, Copy, PartialEq, Eq, Hash`, and a `kind(&self) -> MyErrorKind` method.
+///   As with `predicates`, `accessors` and `into_inner`, nothing is required of the variants' fields, not even `PartialEq` or `Clone`.
+/// - `error_enum(result_alias = "Result")`: Placed on the enum, declares `type Result<T> = std::result::Result<T, MyError>`
+///   next to the enum, with the enum's visibility. Only enums without generic parameters are supported,
+///   as the alias would otherwise have to take them after `T`, which is easier to read written by hand.
+/// - `error_enum(vis = "pub(crate)")`: Placed on the enum, sets the visibility of the methods generated by `predicates`,
+///   `accessors`, `into_inner`, `const_ctors`, `into_anyhow_method` and `kind`, which otherwise have the same visibility as the enum. Trait impls are not affected.
+/// - `error_enum(trace)`: Placed on the enum, makes every generated `From` impl log the conversion with
+///   `tracing::debug!(target: "error_conversion", from = <type name>, "converted")`. Requires the `tracing` feature.
+/// - `error_enum(into_response)`: Placed on the enum, generates `axum::response::IntoResponse` responding with
+///   the `Display` of the error as body. Requires the `axum` feature.
+///   Variants set their status with `#[status(404)]` or `#[status(StatusCode::NOT_FOUND)]`, others respond with 500.
+/// - `error_enum(serialize)`: Placed on the enum, generates `serde::Serialize` writing `{ "kind": "NotFound", "message": "..." }`.
+///   The message is the `Display` generated from `error("...")`, or the `Debug` of the error without it. Fields are never serialized,
+///   so that no internals leak. Requires the `serde` feature.
+/// - `error_enum(from_str(parse_code, ParseCodeError))`: Placed on the enum, generates `std::str::FromStr` delegating to the function,
+///   which takes a `&str` and returns `Result<Self, ParseCodeError>`, e.g. to rebuild an error from a code received over RPC.
+///   The error type is listed as well, since the macro can't read the function's signature.
+/// - `error_enum(debug)`: Placed on the enum, generates a `Debug` impl formatting variants like `#[derive(Debug)]` would,
+///   except for variants marked `#[redact]`, whose fields are all printed as `<redacted>`, e.g. `Login(<redacted>)`,
+///   so that secrets held by an error don't end up in logs. Redacted fields don't need to implement `Debug`.
+///   Like the derive, the impl requires every type parameter of the enum to implement `Debug`. Replaces `#[derive(Debug)]` on the enum.
+/// - `error_enum(std_error)`: Placed on the enum, also generates an `std::error::Error` impl whose `source()` returns the wrapped error.
+///   Variants marked `without_anyhow`, unit variants and variants with several fields have no source.
 ///
 /// # Example
 /// ```rust
@@ -38,109 +204,3757 @@ macro_rules! derive_error {
 ///     CustomError(String),
 /// }
 /// ```
-#[proc_macro_derive(ErrorEnum, attributes(without_anyhow))]
+#[proc_macro_derive(ErrorEnum, attributes(anyhow, without_anyhow, default_anyhow, deref_anyhow, wrap, skip_from, try_from, priority, boxed, arc, from_many, from, from_types, from_display, from_ref, anyhow_variant, context, into_anyhow, via, build_with, bound, deprecated_from, status, redact, error, transparent, flatten, map_anyhow, error_enum))]
 pub fn generate_from_impls(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
 
-    // get enum name
-    let enum_name = &input.ident;
-    let data = &input.data;
+    match expand(&input) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Attribute form of the `ErrorEnum` derive, generating the same impls.
+/// The enum itself is emitted unchanged, apart from the helper attributes being removed.
+/// The options of `#[error_enum(...)]` are passed as arguments instead.
+///
+/// Don't import this attribute in modules deriving `ErrorEnum`, as it would be ambiguous with the derive's
+/// `#[error_enum(...)]` attribute.
+///
+/// # Example
+/// ```rust
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::error_enum;
+///
+/// #[error_enum(predicates)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[without_anyhow]
+///     CustomError(String),
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn error_enum(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input: DeriveInput = parse_macro_input!(item as DeriveInput);
+    let args = TokenStream2::from(args);
+
+    if !args.is_empty() {
+        input.attrs.push(parse_quote!(#[error_enum(#args)]));
+    }
+
+    // Keep emitting the enum on errors, so that they don't cascade into every use of it
+    let generated = expand(&input).unwrap_or_else(|error| error.to_compile_error());
+    remove_helper_attributes(&mut input);
+
+    quote! {
+        #input
+        #generated
+    }.into()
+}
+
+/// Generates the impls of `ErrorEnum` for an enum defined elsewhere in the crate, which can't carry the derive itself.
+/// Placed on a module holding a copy of the enum, with the same variants and fields and the usual helper attributes.
+/// The module is replaced by the impls, the copy only describes the enum named by the argument.
+/// Field types are resolved where the module is, not inside it.
+///
+/// The enum has to be defined in the same crate, as the impls would break the orphan rules otherwise.
+/// `error_enum(kind)` and `error_enum(result_alias = "...")` aren't supported, as the items they declare would not be reachable.
+///
+/// # Example
+/// ```rust
+/// # mod anyhow {
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::error_enum_for;
+///
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///     CustomError(String),
+/// }
+///
+/// #[error_enum_for(MyError)]
+/// mod my_error {
+///     enum MyError {
+///         OtherError(anyhow::Error),
+///
+///         #[without_anyhow]
+///         CustomError(String),
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn error_enum_for(args: TokenStream, item: TokenStream) -> TokenStream {
+    let target = parse_macro_input!(args as Path);
+    let module = parse_macro_input!(item as MirrorModule);
+
+    match expand_for(&target, &module.mirror) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The module `error_enum_for` is placed on, `mod name { enum ... }`, holding nothing but the copy of the enum.
+struct MirrorModule {
+    mirror: DeriveInput,
+}
+
+impl Parse for MirrorModule {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.call(Attribute::parse_outer)?;
+        input.parse::<Visibility>()?;
+        input.parse::<Token![mod]>()?;
+        let name: Ident = input.parse()?;
+
+        let content;
+        braced!(content in input);
+
+        if content.is_empty() {
+            let message = format!("error_enum_for needs a copy of the enum in module `{}`", name);
+            return Err(Error::new_spanned(&name, message));
+        }
+
+        let mirror: DeriveInput = content.parse()?;
+
+        if !content.is_empty() {
+            return Err(content.error("error_enum_for expects the module to hold nothing but the copy of the enum"));
+        }
+
+        Ok(MirrorModule { mirror })
+    }
+}
+
+/// Generates the impls described by `mirror` for the enum `target`.
+/// They are generated for the name of the copy, which is bound to `target` in an anonymous scope.
+fn expand_for(target: &Path, mirror: &DeriveInput) -> Result<TokenStream2, Error> {
+    let model = parse_enum(mirror)?;
+
+    if model.options.kind {
+        return Err(derive_error!("error_enum_for can't generate error_enum(kind), whose enum would not be reachable"));
+    }
 
+    if model.options.result_alias.is_some() {
+        return Err(derive_error!("error_enum_for can't generate error_enum(result_alias), whose alias would not be reachable"));
+    }
+
+    let generated = generate(mirror, &model)?;
+    let name = &mirror.ident;
+
+    Ok(quote! {
+        const _: () = {
+            use #target as #name;
+            #generated
+        };
+    })
+}
+
+/// Converts the expression with `From` and asserts that it lands in the variant, for testing derived conversions.
+/// Panics with the `Debug` of the converted value otherwise, so the enum has to implement `Debug`.
+/// Requires the `testing` feature.
+///
+/// # Example
+/// ```rust
+/// # mod anyhow {
+/// #   #[derive(Debug)]
+/// #   pub struct Error;
+/// # }
+/// use error_conversion_macro::{assert_converts, ErrorEnum};
+///
+/// #[derive(Debug, ErrorEnum)]
+/// enum MyError {
+///     OtherError(anyhow::Error),
+///
+///     #[without_anyhow]
+///     CustomError(String),
+/// }
+///
+/// assert_converts!(String::from("invalid") => MyError::CustomError);
+/// assert_converts!(anyhow::Error => MyError::OtherError);
+/// ```
+#[cfg(feature = "testing")]
+#[proc_macro]
+pub fn assert_converts(input: TokenStream) -> TokenStream {
+    match expand_assert_converts(input.into()) {
+        Ok(stream) => stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Splits `source => Enum::Variant` at the arrow, as syn only parses the full expression grammar with its `full` feature.
+#[cfg(feature = "testing")]
+fn expand_assert_converts(input: TokenStream2) -> Result<TokenStream2, Error> {
+    use proc_macro2::{Spacing, TokenTree};
+
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let arrow = tokens.windows(2).position(|pair| match pair {
+        [TokenTree::Punct(first), TokenTree::Punct(second)] => {
+            first.as_char() == '=' && first.spacing() == Spacing::Joint && second.as_char() == '>'
+        }
+        _ => false,
+    });
+
+    let arrow = match arrow {
+        Some(arrow) if arrow > 0 => arrow,
+        _ => return Err(derive_error!("expected `source => Enum::Variant`")),
+    };
+
+    let source: TokenStream2 = tokens[..arrow].iter().cloned().collect();
+    let variant: Path = syn::parse2(tokens[arrow + 2..].iter().cloned().collect())?;
+    let expected = variant.to_token_stream().to_string().replace(' ', "");
+
+    Ok(quote! {
+        match ::core::convert::Into::into(#source) {
+            #variant { .. } => {}
+            #[allow(unreachable_patterns)]
+            other => ::core::panic!("expected a conversion into `{}`, got {:?}", #expected, other),
+        }
+    })
+}
+
+/// Helper attributes of the derive, which the attribute form has to remove from the enum it emits.
+/// Keep in sync with the `attributes(...)` of `ErrorEnum`.
+const HELPER_ATTRIBUTES: &[&str] = &["anyhow", "without_anyhow", "default_anyhow", "deref_anyhow", "wrap", "skip_from", "try_from", "priority", "boxed", "arc", "from_many", "from", "from_types", "from_display", "from_ref", "anyhow_variant", "context", "into_anyhow", "via", "build_with", "bound", "deprecated_from", "status", "redact", "error", "transparent", "flatten", "map_anyhow", "error_enum"];
+
+fn remove_helper_attributes(input: &mut DeriveInput) {
+    let is_helper = |attr: &Attribute| HELPER_ATTRIBUTES.iter().any(|name| attr.path().is_ident(name));
+
+    input.attrs.retain(|attr| !is_helper(attr));
+
+    if let Data::Enum(data_enum) = &mut input.data {
+        for variant in &mut data_enum.variants {
+            variant.attrs.retain(|attr| !is_helper(attr));
+
+            for field in variant.fields.iter_mut() {
+                field.attrs.retain(|attr| !is_helper(attr));
+            }
+        }
+    }
+}
+
+/// Combines the collected errors into one, so that every problem is reported at once.
+fn combine_errors(errors: Vec<Error>) -> Result<(), Error> {
+    match errors.into_iter().reduce(|mut combined, error| {
+        combined.combine(error);
+        combined
+    }) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Generates every impl for the enum, shared by the derive and attribute forms.
+/// Only deals in `proc_macro2` tokens, so it can also be called outside of a macro expansion, like in unit tests.
+pub(crate) fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let model = parse_enum(input)?;
+    generate(input, &model)
+}
+
+/// The enum as seen by the generators, with the aggregate looked up and the attributes of every variant read once.
+struct EnumModel<'a> {
+    options: Options,
+    /// The aggregate variant, `None` with `error_enum(no_aggregate)`.
+    aggregate: Option<Aggregate<'a>>,
+    variants: Vec<VariantModel<'a>>,
+}
+
+impl EnumModel<'_> {
+    fn is_aggregate(&self, variant: &Variant) -> bool {
+        self.aggregate.as_ref().is_some_and(|aggregate| aggregate.variant.ident == variant.ident)
+    }
+}
+
+/// A variant together with the flags set on it.
+struct VariantModel<'a> {
+    variant: &'a Variant,
+    skip_from: bool,
+    try_from: bool,
+    without_anyhow: bool,
+    wrap: bool,
+    boxed: bool,
+    arc: bool,
+    /// Whether the field is a `Vec` filled from single elements and iterators of them.
+    from_many: bool,
+    from_ref: bool,
+    /// Whether `Display` and `source()` forward to the inner error.
+    transparent: bool,
+    /// Whether the generated `Debug` hides the fields of the variant.
+    redact: bool,
+    /// The variants of the inner enum listed in `#[flatten(...)]`, lifted into our variants of the same name.
+    flatten: Vec<Ident>,
+    /// How the variant holds the aggregate, if it is the aggregate variant or another variant of the aggregate type.
+    holds_aggregate: Option<AggregateField>,
+    /// Position among the downcasts of `try_from` and `downcast_from_anyhow`, lower ones being tried first.
+    priority: i32,
+    /// The types listed in `#[from_types(...)]`.
+    from_types: Vec<Type>,
+    /// The types listed in `#[from_display(...)]`, stored as their message.
+    from_display: Vec<Type>,
+    /// The closure of `#[build_with(...)]`, building the enum from the inner value.
+    build_with: Option<BuilderClosure>,
+    /// The predicates of `#[bound("...")]`, added to the where-clause of the variant's impls.
+    bounds: Vec<WherePredicate>,
+}
+
+fn parse_enum(input: &DeriveInput) -> Result<EnumModel<'_>, Error> {
     // Validate that ErrorEnum is only implemented for enums
-    let enum_data = match data {
+    let enum_data = match &input.data {
         Data::Enum(data_enum) => data_enum,
-        _ => return derive_error!("ErrorEnum is only implemented for enums"),
+        _ => return Err(derive_error!("ErrorEnum is only implemented for enums")),
     };
 
-    // A vector to store the generated impl From tokens
-    let mut generated_tokens = Vec::new();
+    if enum_data.variants.is_empty() {
+        return Err(derive_error!(input.ident.span(), format!("ErrorEnum requires at least one variant; `{}` has none", input.ident)));
+    }
 
-    // Find enum variant with anyhow::Error type
-    let anyhow_variant = match get_variant_with_type(&enum_data.variants, "anyhow :: Error") {
-        Some(variant) => variant,
-        None => return derive_error!("Could not find a variant with anyhow::Error type in this enum")
+    let mut options = parse_options(&input.attrs)?;
+
+    if !options.aggregate_any_of.is_empty() && !options.no_aggregate {
+        options.aggregate = pick_aggregate(&input.ident, &enum_data.variants, &options.aggregate_any_of)?;
+    }
+
+    // Find the enum variants with the aggregate error type, unless the enum has none
+    let candidates = match options.no_aggregate {
+        true => Vec::new(),
+        false => get_aggregates(&enum_data.variants, &options.aggregate)?,
     };
 
-    // Generate impls
+    let mut variants = Vec::new();
+    let mut errors = Vec::new();
+
     for variant in &enum_data.variants {
-        if &variant.ident == anyhow_variant {
-            continue;
+        match parse_variant(variant) {
+            Ok(model) => variants.push(model),
+            Err(error) => errors.push(error),
         }
+    }
 
-        let token_stream = generate_impl(enum_name, variant, anyhow_variant);
-        if let Some(stream) = token_stream {
-            generated_tokens.push(stream);
+    // Variants holding the aggregate hold it themselves, there is nothing to convert without it
+    for candidate in &candidates {
+        if let Some(attr) = candidate.variant.attrs.iter().find(|attr| attr.path().is_ident("without_anyhow")) {
+            let message = format!(
+                "#[without_anyhow] is meaningless on `{}`, the aggregate variant holding the {} itself",
+                candidate.variant.ident, type_name(&options.aggregate)
+            );
+            errors.push(Error::new_spanned(attr, message));
+        }
+
+        if let Some(model) = variants.iter_mut().find(|model| model.variant.ident == candidate.variant.ident) {
+            model.holds_aggregate = Some(candidate.field);
         }
     }
 
-    quote! {
-        #(#generated_tokens)*
-        impl From<anyhow::Error> for #enum_name {
-            fn from(value: anyhow::Error) -> Self {
-                #enum_name::#anyhow_variant(value.into())
-            }
+    combine_errors(errors)?;
+
+    let aggregate = match options.no_aggregate {
+        true => None,
+        false => Some(get_default_aggregate(&input.ident, &enum_data.variants, candidates, &options.aggregate)?),
+    };
+
+    Ok(EnumModel { options, aggregate, variants })
+}
+
+/// Variant attributes that can't be combined, with what goes wrong if they are.
+const CONFLICTING_ATTRIBUTES: &[(&str, &str, &str)] = &[
+    ("transparent", "error", "displays the inner error instead of an #[error] message"),
+    ("without_anyhow", "wrap", "only #[wrap] treats the wrapped type as an error"),
+    ("from", "without_anyhow", "#[from] selects the field to convert from and belongs on that field"),
+    ("without_anyhow", "context", "context is only attached in the aggregate"),
+    ("without_anyhow", "into_anyhow", "contradict each other"),
+    ("without_anyhow", "map_anyhow", "nothing is extracted to map"),
+    ("context", "into_anyhow", "both route it into the aggregate"),
+    ("without_anyhow", "via", "contradict each other"),
+    ("context", "via", "both route it into the aggregate"),
+    ("into_anyhow", "via", "both route it into the aggregate"),
+    ("build_with", "via", "the closure builds the whole value"),
+    ("build_with", "context", "the closure builds the whole value"),
+    ("build_with", "into_anyhow", "the closure builds the whole value"),
+    ("build_with", "map_anyhow", "the closure builds the whole value"),
+    ("build_with", "flatten", "the closure builds the whole value"),
+    ("boxed", "arc", "its field can only be one pointer"),
+    ("boxed", "from_many", "its field can't be both a Box and a Vec"),
+    ("arc", "from_many", "its field can't be both an Arc and a Vec"),
+];
+
+/// Rejects combinations of variant attributes listed in `CONFLICTING_ATTRIBUTES`,
+/// reporting each at whichever of the two attributes comes second.
+fn check_conflicting_attributes(variant: &Variant) -> Result<(), Error> {
+    let position = |name: &str| variant.attrs.iter().position(|attr| attr.path().is_ident(name));
+    let mut errors = Vec::new();
+
+    for (first, second, reason) in CONFLICTING_ATTRIBUTES {
+        if let (Some(first_position), Some(second_position)) = (position(first), position(second)) {
+            let message = format!("Variant `{}` is marked with #[{}] and #[{}], but {}", variant.ident, first, second, reason);
+            errors.push(Error::new_spanned(&variant.attrs[first_position.max(second_position)], message));
         }
-    }.into()
+    }
+
+    combine_errors(errors)
 }
 
-fn get_unnamed_field(variant: &Variant) -> Option<&FieldsUnnamed> {
-    match &variant.fields {
-        Fields::Unnamed(field) => Some(field),
-        _ => None
+fn parse_variant(variant: &Variant) -> Result<VariantModel<'_>, Error> {
+    check_conflicting_attributes(variant)?;
+
+    let transparent = has_flag_attribute(&variant.attrs, "transparent")?;
+
+    if transparent && get_single_field_type(variant).is_none() {
+        let message = format!("Variant `{}` is marked with #[transparent], but has no single field to forward to", variant.ident);
+        return Err(Error::new_spanned(&variant.ident, message));
     }
+
+    Ok(VariantModel {
+        variant,
+        skip_from: has_flag_attribute(&variant.attrs, "skip_from")?,
+        try_from: has_flag_attribute(&variant.attrs, "try_from")?,
+        without_anyhow: has_flag_attribute(&variant.attrs, "without_anyhow")?,
+        wrap: has_flag_attribute(&variant.attrs, "wrap")?,
+        boxed: has_flag_attribute(&variant.attrs, "boxed")?,
+        arc: has_flag_attribute(&variant.attrs, "arc")?,
+        from_many: has_flag_attribute(&variant.attrs, "from_many")?,
+        from_ref: has_flag_attribute(&variant.attrs, "from_ref")?,
+        transparent,
+        redact: has_flag_attribute(&variant.attrs, "redact")?,
+        flatten: get_flatten_variants(variant)?,
+        holds_aggregate: None,
+        priority: get_priority(variant)?,
+        from_types: get_type_list(variant, "from_types")?,
+        from_display: get_type_list(variant, "from_display")?,
+        build_with: get_builder_closure(variant)?,
+        bounds: get_bounds(variant)?,
+    })
 }
 
-fn get_variant_with_type<'a>(variants: &'a Punctuated<Variant, Comma>, with_type: &str) -> Option<&'a Ident> {
-    variants.iter().find_map(|variant| {
-        if let Some(field) = get_unnamed_field(variant) {
-            let variant_name = &variant.ident;
-            let variant_inner_type = &field.unnamed;
-            let variant_inner_type_str = variant_inner_type.into_token_stream().to_string();
+/// Generates the impls described by `model` for the enum `input`.
+fn generate(input: &DeriveInput, model: &EnumModel) -> Result<TokenStream2, Error> {
+    let enum_name = &input.ident;
+    let options = &model.options;
+    let aggregate = model.aggregate.as_ref();
+    let variants = &model.variants;
+
+    // A vector to store the generated impl From tokens
+    let mut generated_tokens = Vec::new();
+
+    // Problems of independent variants are collected, so that they are all reported at once
+    let mut errors = Vec::new();
+
+    // Variants left without a From impl are reported before anything else, as they would otherwise fail on their own
+    if options.strict {
+        check_strict(variants)?;
+    }
+
+    // Only the attribute form sees gated variants, which the impls matching on every variant can't leave out yet
+    check_cfg_variants(options, variants)?;
+
+    // Two variants wrapping the same type would generate conflicting From impls
+    if let Err(error) = check_duplicate_types(variants, aggregate, &options.catch_all_into_anyhow) {
+        errors.push(error);
+    }
+
+    // Generate impls
+    for variant in variants {
+        match generate_variant_impls(options, enum_name, &input.generics, variant, variants, aggregate) {
+            Ok(streams) => generated_tokens.extend(streams),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    combine_errors(errors)?;
+
+    if let Some(model) = variants.iter().find(|model| !options.downcast_from_anyhow && !model.try_from && model.variant.attrs.iter().any(|attr| attr.path().is_ident("priority"))) {
+        let message = format!("Variant `{}` is marked with #[priority], which only orders downcasts with #[try_from] or error_enum(downcast_from_anyhow)", model.variant.ident);
+        return Err(Error::new_spanned(&model.variant.ident, message));
+    }
 
-            if &*variant_inner_type_str == with_type {
-                return Some(variant_name);
+    if let Some(from_type) = options.catch_all_into_anyhow.first().filter(|_| aggregate.is_none()) {
+        return Err(Error::new_spanned(from_type, "catch_all_into_anyhow converts into the aggregate variant, which the enum doesn't have"));
+    }
+
+    if let Some(aggregate) = aggregate {
+        let anyhow_variant = &aggregate.variant.ident;
+        let anyhow_type = &aggregate.ty;
+
+        generated_tokens.extend(generate_catch_all_impls(options, enum_name, &input.generics, aggregate));
+
+        let skip_aggregate_from = options.no_anyhow_from || variants.iter().any(|variant| variant.skip_from && model.is_aggregate(variant.variant));
+
+        // A TryFrom impl downcasting the aggregate replaces its From impl, since the two would conflict
+        if options.downcast_from_anyhow {
+            if skip_aggregate_from {
+                return Err(derive_error!("downcast_from_anyhow generates the From impl for the aggregate and cannot be combined with leaving it out"));
             }
+
+            generated_tokens.push(generate_downcast_from_impl(options, enum_name, &input.generics, variants, aggregate)?);
+        } else if let Some(stream) = generate_try_from_impl(options, enum_name, &input.generics, variants, aggregate)? {
+            generated_tokens.push(stream);
+        } else if !skip_aggregate_from {
+            let from = options.convert_from();
+            let trace = options.trace_conversion(anyhow_type);
+            let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+            generated_tokens.push(quote! {
+                #[automatically_derived]
+                #[allow(clippy::all)]
+                impl #impl_generics #from<#anyhow_type> for #enum_name #ty_generics #where_clause {
+                    fn from(value: #anyhow_type) -> Self {
+                        #trace
+                        #enum_name::#anyhow_variant(value.into())
+                    }
+                }
+            });
+        }
+    }
+
+    let display = generate_display_impl(options, enum_name, &input.generics, variants)?;
+    let has_display = display.is_some();
+    generated_tokens.extend(display);
+
+    if options.debug {
+        generated_tokens.push(generate_debug_impl(options, enum_name, &input.generics, variants));
+    } else if let Some(model) = variants.iter().find(|model| model.redact) {
+        let message = format!("Variant `{}` is marked with #[redact], which only takes effect with error_enum(debug)", model.variant.ident);
+        return Err(Error::new_spanned(&model.variant.ident, message));
+    }
+
+    if options.std_error {
+        generated_tokens.push(generate_std_error_impl(options, enum_name, &input.generics, variants)?);
+    }
+
+    let vis = options.vis.as_ref().unwrap_or(&input.vis);
+
+    if options.predicates {
+        generated_tokens.push(generate_predicates(options, vis, enum_name, &input.generics, variants)?);
+    }
+
+    if options.accessors {
+        generated_tokens.push(generate_accessors(options, vis, enum_name, &input.generics, variants)?);
+    }
+
+    if options.into_inner {
+        generated_tokens.push(generate_into_inner(options, vis, enum_name, &input.generics, variants)?);
+    }
+
+    if options.const_ctors {
+        generated_tokens.push(generate_const_ctors(options, vis, enum_name, &input.generics, variants)?);
+    }
+
+    generated_tokens.extend(generate_deprecated_ctors(options, vis, enum_name, &input.generics, variants)?);
+
+    // The kind enum is a named item, declared alongside the enum rather than among the impls
+    let mut definitions = Vec::new();
+
+    if options.kind {
+        let (definition, kind_impl) = generate_kind(options, vis, input, variants);
+        definitions.push(definition);
+        generated_tokens.push(kind_impl);
+    }
+
+    if let Some(alias) = &options.result_alias {
+        if let Some(param) = input.generics.params.first() {
+            return Err(Error::new_spanned(param, "result_alias only supports enums without generic parameters, the alias has to be written by hand otherwise"));
+        }
+
+        let std = options.std_crate();
+        let doc = format!("The result of operations failing with [`{}`].", enum_name);
+        let visibility = &input.vis;
+        definitions.push(quote! {
+            #[doc = #doc]
+            #visibility type #alias<T> = #std::result::Result<T, #enum_name>;
+        });
+    }
+
+    if options.into_response {
+        generated_tokens.push(generate_into_response_impl(enum_name, &input.generics, variants)?);
+    } else if let Some(attr) = variants.iter().flat_map(|model| &model.variant.attrs).find(|attr| attr.path().is_ident("status")) {
+        return Err(Error::new_spanned(attr, "#[status] only takes effect with error_enum(into_response)"));
+    }
+
+    if options.serialize {
+        generated_tokens.push(generate_serialize_impl(options, enum_name, &input.generics, variants, has_display));
+    }
+
+    if let Some((function, error)) = &options.from_str {
+        generated_tokens.push(generate_from_str_impl(options, enum_name, &input.generics, function, error));
+    }
+
+    if options.into_aggregate {
+        if options.std_error {
+            return Err(derive_error!("into_aggregate cannot be combined with std_error, as the aggregate already converts from every std::error::Error"));
+        }
+
+        if options.no_std {
+            return Err(derive_error!("into_aggregate needs std to build messages and cannot be combined with no_std"));
+        }
+
+        generated_tokens.push(generate_into_aggregate_impl(options, enum_name, &input.generics, variants, aggregate));
+    }
+
+    if options.into_anyhow_method {
+        if options.no_std {
+            return Err(derive_error!("into_anyhow_method needs std to build messages and cannot be combined with no_std"));
+        }
+
+        // `into_inner` already names the method of a variant called `Anyhow` like this
+        if let Some(model) = variants.iter().find(|model| options.into_inner && snake_case(&model.variant.ident) == "anyhow" && get_single_field_type(model.variant).is_some()) {
+            let message = format!("into_anyhow_method and into_inner would both generate `into_anyhow`, the latter for `{}`", model.variant.ident);
+            return Err(Error::new_spanned(&model.variant.ident, message));
         }
 
-        None
+        generated_tokens.push(generate_into_anyhow_method(options, vis, enum_name, &input.generics, variants, aggregate));
+    }
+
+    Ok(quote! {
+        #(#definitions)*
+        #(#generated_tokens)*
     })
 }
 
-fn generate_impl(enum_name: &Ident, variant: &Variant, anyhow_variant: &Ident) -> Option<TokenStream2> {
-    let field = match get_unnamed_field(variant) {
-        Some(field) => field,
-        None => return None,
-    };
+/// Options set through the `#[error_enum(...)]` attribute on the enum.
+struct Options {
+    /// The aggregate error type, `anyhow::Error` unless configured otherwise.
+    aggregate: Type,
+    /// Whether `aggregate_kind = "struct"` declares the aggregate a plain struct that is only converted with `From`.
+    struct_aggregate: bool,
+    /// The types of `aggregate_any_of(...)`, the one held by the enum becoming `aggregate`.
+    aggregate_any_of: Vec<Type>,
+    /// The types of `catch_all_into_anyhow(...)`, converted into the aggregate variant.
+    catch_all_into_anyhow: Vec<Type>,
+    /// Whether to generate an `std::error::Error` impl.
+    std_error: bool,
+    /// Whether the enum has no aggregate variant, so that every variant is simply wrapped.
+    no_aggregate: bool,
+    /// Whether to generate the reverse conversion from the enum into the aggregate.
+    into_aggregate: bool,
+    /// Whether to generate the reverse conversion as an `into_anyhow` method instead of a `From` impl.
+    into_anyhow_method: bool,
+    /// Whether to leave out the `From` impl for the aggregate itself.
+    no_anyhow_from: bool,
+    /// Whether the `From` impl for the aggregate downcasts it into the variants before falling back to the aggregate variant.
+    downcast_from_anyhow: bool,
+    /// Whether every variant not holding the aggregate has to get a `From` impl unless marked otherwise.
+    strict: bool,
+    /// Whether generated code may only reference `core` (and `alloc` for `#[boxed]` and `#[arc]`).
+    no_std: bool,
+    /// Whether to generate `is_<variant>` methods.
+    predicates: bool,
+    /// Whether to generate `as_<variant>` methods.
+    accessors: bool,
+    /// Prefix of the methods generated by `predicates`, `is_` unless configured otherwise.
+    predicate_prefix: String,
+    /// Prefix of the methods generated by `accessors`, `as_` unless configured otherwise.
+    accessor_prefix: String,
+    /// Whether to generate consuming `into_<variant>` methods.
+    into_inner: bool,
+    /// Whether to generate `const fn` constructors named after the variants.
+    const_ctors: bool,
+    /// Whether to generate a fieldless `<Enum>Kind` enum and a `kind()` method.
+    kind: bool,
+    /// Name of the `Result` alias to declare for the enum, if any.
+    result_alias: Option<Ident>,
+    /// Whether generated `From` impls log every conversion with `tracing::debug!`.
+    trace: bool,
+    /// Whether to generate an axum `IntoResponse` impl.
+    into_response: bool,
+    /// Whether to generate a serde `Serialize` impl.
+    serialize: bool,
+    /// Whether to generate a `Debug` impl honoring `#[redact]`.
+    debug: bool,
+    /// The function and error type of `from_str(...)`, for a `FromStr` impl delegating to the function.
+    from_str: Option<(Path, Type)>,
+    /// Whether the enum is `#[non_exhaustive]`, read from the enum's own attributes rather than `error_enum`.
+    non_exhaustive: bool,
+    /// Visibility of the generated inherent methods, the enum's own visibility if not set.
+    vis: Option<Visibility>,
+}
 
-    let variant_name = &variant.ident;
-    let variant_inner_type = &field.unnamed;
+impl Options {
+    /// Crate providing `fmt` and `error` to generated code.
+    fn std_crate(&self) -> TokenStream2 {
+        match self.no_std {
+            true => quote!(core),
+            false => quote!(std),
+        }
+    }
 
-    // Check for the presence of `without_anyhow` attribute
-    let without_anyhow_attribute = variant
-        .attrs
-        .iter()
-        .find(|attr| attr.meta.clone().into_token_stream().to_string() == "without_anyhow");
+    fn convert_from(&self) -> TokenStream2 {
+        match self.no_std {
+            true => quote!(core::convert::From),
+            false => quote!(From),
+        }
+    }
 
-    match without_anyhow_attribute {
-        // Generate the full From implementation, extracting anyhow::Error from the variant type.
-        None => quote! {
-                    impl From<#variant_inner_type> for #enum_name {
-                        fn from(value: #variant_inner_type) -> Self {
-                            match value {
-                                #variant_inner_type::#anyhow_variant(e) => #enum_name::#anyhow_variant(e),
-                                _ => #enum_name::#variant_name(value),
-                            }
-                        }
-                    }
-                }.into(),
+    fn convert_try_from(&self) -> TokenStream2 {
+        match self.no_std {
+            true => quote!(core::convert::TryFrom),
+            false => quote!(TryFrom),
+        }
+    }
 
-        // Don't extract anyhow::Error from the variant type, instead just wrap the type in our enum.
-        Some(_) => quote! {
-                    impl From<#variant_inner_type> for #enum_name {
-                        fn from(value: #variant_inner_type) -> Self {
-                            Self::#variant_name(value)
-                        }
-                    }
-                }.into()
+    fn box_type(&self) -> TokenStream2 {
+        match self.no_std {
+            true => quote!(alloc::boxed::Box),
+            false => quote!(Box),
+        }
+    }
+
+    fn vec_macro(&self) -> TokenStream2 {
+        match self.no_std {
+            true => quote!(alloc::vec!),
+            false => quote!(std::vec!),
+        }
+    }
+
+    fn arc_type(&self) -> TokenStream2 {
+        match self.no_std {
+            true => quote!(alloc::sync::Arc),
+            false => quote!(std::sync::Arc),
+        }
+    }
+
+    /// Statement logging a conversion from `from_type`, if `trace` is set.
+    fn trace_conversion(&self, from_type: &Type) -> TokenStream2 {
+        if !self.trace {
+            return quote!();
+        }
+
+        let std = self.std_crate();
+        quote! {
+            tracing::debug!(target: "error_conversion", from = #std::any::type_name::<#from_type>(), "converted");
+        }
+    }
+
+    /// Wildcard arm closing generated matches on a `#[non_exhaustive]` enum, so they stay valid if the matches
+    /// ever end up outside the defining crate. Within it, every variant is already matched.
+    fn fallback_arm(&self) -> TokenStream2 {
+        match self.non_exhaustive {
+            true => quote! {
+                #[allow(unreachable_patterns)]
+                _ => unreachable!(),
+            },
+            false => quote!(),
+        }
+    }
+
+    /// Whether the aggregate is a boxed trait object such as `Box<dyn std::error::Error + Send + Sync>`.
+    /// Such an aggregate can't be matched on like an inner enum and is built with `From` rather than `new` and `msg`.
+    fn trait_object_aggregate(&self) -> bool {
+        get_box_inner_type(&self.aggregate).is_some_and(|inner| matches!(inner, Type::TraitObject(_)))
     }
-}
\ No newline at end of file
+
+    /// Whether the aggregate is matched on and built with `new` and `msg` like `anyhow::Error`, or is only converted with `From`.
+    /// This is the case for boxed trait objects and for aggregates declared with `aggregate_kind = "struct"`.
+    fn opaque_aggregate(&self) -> bool {
+        self.struct_aggregate || self.trait_object_aggregate()
+    }
+
+    /// Method of the aggregate attaching a `#[context]` message, as eyre calls it `wrap_err`.
+    fn context_method(&self) -> Ident {
+        match types_match(&self.aggregate, &parse_quote!(eyre::Report)) {
+            true => Ident::new("wrap_err", Span::call_site()),
+            false => Ident::new("context", Span::call_site()),
+        }
+    }
+}
+
+/// The keys accepted by `#[error_enum(...)]`, listed when an unknown one is used.
+const OPTION_KEYS: &[&str] = &[
+    "aggregate", "aggregate_kind", "aggregate_any_of", "catch_all_into_anyhow", "vis", "predicate_prefix", "accessor_prefix", "std_error", "no_aggregate", "into_aggregate",
+    "into_anyhow_method", "no_anyhow_from", "downcast_from_anyhow", "strict", "no_std", "predicates", "accessors", "into_inner", "const_ctors", "kind", "result_alias",
+    "trace", "into_response", "serialize", "debug", "from_str",
+];
+
+fn parse_options(attrs: &[Attribute]) -> Result<Options, Error> {
+    let mut options = Options {
+        aggregate: parse_quote!(anyhow::Error),
+        struct_aggregate: false,
+        aggregate_any_of: Vec::new(),
+        catch_all_into_anyhow: Vec::new(),
+        std_error: false,
+        no_aggregate: false,
+        into_aggregate: false,
+        into_anyhow_method: false,
+        no_anyhow_from: false,
+        downcast_from_anyhow: false,
+        strict: false,
+        no_std: false,
+        predicates: false,
+        accessors: false,
+        predicate_prefix: String::from("is_"),
+        accessor_prefix: String::from("as_"),
+        into_inner: false,
+        const_ctors: false,
+        kind: false,
+        result_alias: None,
+        trace: false,
+        into_response: false,
+        serialize: false,
+        debug: false,
+        from_str: None,
+        non_exhaustive: attrs.iter().any(|attr| attr.path().is_ident("non_exhaustive")),
+        vis: None,
+    };
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("error_enum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("aggregate") {
+                let aggregate: LitStr = meta.value()?.parse()?;
+                options.aggregate = aggregate.parse()?;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("aggregate_kind") {
+                let kind: LitStr = meta.value()?.parse()?;
+                options.struct_aggregate = match kind.value().as_str() {
+                    "struct" => true,
+                    "enum" => false,
+                    _ => return Err(Error::new_spanned(kind, "expected aggregate_kind = \"struct\" or \"enum\"")),
+                };
+                return Ok(());
+            }
+
+            if meta.path.is_ident("aggregate_any_of") {
+                let content;
+                parenthesized!(content in meta.input);
+
+                for aggregate in Punctuated::<LitStr, Comma>::parse_terminated(&content)? {
+                    options.aggregate_any_of.push(aggregate.parse()?);
+                }
+
+                return Ok(());
+            }
+
+            if meta.path.is_ident("catch_all_into_anyhow") {
+                let content;
+                parenthesized!(content in meta.input);
+                options.catch_all_into_anyhow.extend(Punctuated::<Type, Comma>::parse_terminated(&content)?);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("vis") {
+                let vis: LitStr = meta.value()?.parse()?;
+                options.vis = Some(vis.parse()?);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("predicate_prefix") {
+                options.predicate_prefix = parse_method_prefix(&meta.value()?.parse()?)?;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("accessor_prefix") {
+                options.accessor_prefix = parse_method_prefix(&meta.value()?.parse()?)?;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("std_error") {
+                options.std_error = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("no_aggregate") {
+                options.no_aggregate = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("into_aggregate") {
+                options.into_aggregate = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("into_anyhow_method") {
+                options.into_anyhow_method = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("no_anyhow_from") {
+                options.no_anyhow_from = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("downcast_from_anyhow") {
+                options.downcast_from_anyhow = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("strict") {
+                options.strict = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("no_std") {
+                options.no_std = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("predicates") {
+                options.predicates = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("accessors") {
+                options.accessors = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("into_inner") {
+                options.into_inner = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("const_ctors") {
+                options.const_ctors = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("kind") {
+                options.kind = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("result_alias") {
+                let alias: LitStr = meta.value()?.parse()?;
+                options.result_alias = Some(alias.parse()?);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("trace") {
+                if !cfg!(feature = "tracing") {
+                    return Err(meta.error("the trace option requires the `tracing` feature of error-conversion-macro"));
+                }
+
+                options.trace = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("into_response") {
+                if !cfg!(feature = "axum") {
+                    return Err(meta.error("the into_response option requires the `axum` feature of error-conversion-macro"));
+                }
+
+                options.into_response = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("from_str") {
+                let content;
+                parenthesized!(content in meta.input);
+
+                let function: Path = content.parse()?;
+                if content.parse::<Option<Comma>>()?.is_none() || content.is_empty() {
+                    return Err(meta.error("from_str expects the parsing function and its error type, such as from_str(parse_code, ParseCodeError)"));
+                }
+
+                options.from_str = Some((function, content.parse()?));
+                content.parse::<Option<Comma>>()?;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("debug") {
+                options.debug = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("serialize") {
+                if !cfg!(feature = "serde") {
+                    return Err(meta.error("the serialize option requires the `serde` feature of error-conversion-macro"));
+                }
+
+                options.serialize = true;
+                return Ok(());
+            }
+
+            let name = meta.path.to_token_stream().to_string().replace(' ', "");
+            Err(meta.error(format!("unknown error_enum option `{}`, expected one of: {}", name, OPTION_KEYS.join(", "))))
+        })?;
+    }
+
+    if options.predicates && options.accessors && options.predicate_prefix == options.accessor_prefix {
+        let message = format!("predicates and accessors would both generate methods prefixed `{}`", options.predicate_prefix);
+        return Err(derive_error!(message));
+    }
+
+    Ok(options)
+}
+
+/// Validates that `prefix` can start a method name, such as `is_` or `variant_is_`.
+fn parse_method_prefix(prefix: &LitStr) -> Result<String, Error> {
+    let value = prefix.value();
+    let mut chars = value.chars();
+
+    match chars.next() {
+        Some(first) if (first.is_ascii_alphabetic() || first == '_') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_') => Ok(value),
+        _ => Err(Error::new_spanned(prefix, format!("`{}` is not a valid prefix for method names", value))),
+    }
+}
+
+/// The shape of the fields of a variant, which every generated pattern and constructor branches on.
+#[derive(Clone, Copy)]
+enum VariantShape<'a> {
+    Unit,
+    Tuple(&'a Punctuated<Field, Comma>),
+    Named(&'a Punctuated<Field, Comma>),
+}
+
+impl<'a> VariantShape<'a> {
+    fn of(variant: &'a Variant) -> Self {
+        match &variant.fields {
+            Fields::Unit => VariantShape::Unit,
+            Fields::Unnamed(fields) => VariantShape::Tuple(&fields.unnamed),
+            Fields::Named(fields) => VariantShape::Named(&fields.named),
+        }
+    }
+
+    /// The only field, if the variant has exactly one.
+    fn single_field(self) -> Option<&'a Field> {
+        match self {
+            VariantShape::Tuple(fields) | VariantShape::Named(fields) if fields.len() == 1 => fields.first(),
+            _ => None,
+        }
+    }
+
+    /// The only field of a tuple variant such as `Other(anyhow::Error)`, the shape aggregate variants have.
+    fn single_tuple_field(self) -> Option<&'a Field> {
+        match self {
+            VariantShape::Tuple(_) => self.single_field(),
+            _ => None,
+        }
+    }
+}
+
+/// The variant holding the aggregate error.
+struct Aggregate<'a> {
+    variant: &'a Variant,
+    /// The aggregate type exactly as it was written in the enum.
+    ty: Type,
+    /// How the variant's field holds the aggregate.
+    field: AggregateField,
+}
+
+/// How the field of the aggregate variant holds the aggregate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AggregateField {
+    /// The field is the aggregate itself.
+    Plain,
+    /// The field is a `Box` of the aggregate.
+    Boxed,
+    /// The field is a `#[deref_anyhow]` newtype around the aggregate.
+    Newtype,
+}
+
+impl Aggregate<'_> {
+    /// Whether values of the aggregate type have to be converted with `.into()` to fill the variant.
+    fn converted(&self) -> bool {
+        self.field != AggregateField::Plain
+    }
+}
+
+/// Finds the variant marked with `#[deref_anyhow]`, or else every variant wrapping a single type
+/// structurally equal to `with_type` or a `Box` of it.
+fn get_aggregates<'a>(variants: &'a Punctuated<Variant, Comma>, with_type: &Type) -> Result<Vec<Aggregate<'a>>, Error> {
+    for variant in variants {
+        if !has_flag_attribute(&variant.attrs, "deref_anyhow")? {
+            continue;
+        }
+
+        return match VariantShape::of(variant).single_tuple_field() {
+            Some(_) => Ok(vec![Aggregate { variant, ty: with_type.clone(), field: AggregateField::Newtype }]),
+            _ => {
+                let message = format!("Variant `{}` is marked with #[deref_anyhow] but doesn't wrap a single newtype", variant.ident);
+                Err(Error::new_spanned(&variant.ident, message))
+            }
+        };
+    }
+
+    let detected: Vec<Aggregate> = variants.iter().filter_map(|variant| {
+        let variant_inner_type = &VariantShape::of(variant).single_tuple_field()?.ty;
+
+        if types_match(variant_inner_type, with_type) {
+            return Some(Aggregate { variant, ty: variant_inner_type.clone(), field: AggregateField::Plain });
+        }
+
+        match get_box_inner_type(variant_inner_type) {
+            Some(ty) if types_match(ty, with_type) => Some(Aggregate { variant, ty: ty.clone(), field: AggregateField::Boxed }),
+            _ => None,
+        }
+    }).collect();
+
+    // A variant marked with #[anyhow] is the aggregate whatever its type is written as, e.g. through a type alias
+    let mut marked = None;
+
+    for variant in variants {
+        if has_flag_attribute(&variant.attrs, "anyhow")? {
+            marked = Some(variant);
+            break;
+        }
+    }
+
+    let variant = match marked {
+        Some(variant) => variant,
+        None => return Ok(detected),
+    };
+
+    if let Some(other) = detected.iter().find(|candidate| candidate.variant.ident != variant.ident) {
+        let message = format!(
+            "Variant `{}` is marked with #[anyhow], but `{}` holds {} as well",
+            variant.ident, other.variant.ident, type_name(with_type)
+        );
+        return Err(Error::new_spanned(&other.variant.ident, message));
+    }
+
+    match VariantShape::of(variant).single_tuple_field() {
+        Some(field) => {
+            let ty = &field.ty;
+
+            Ok(vec![match get_box_inner_type(ty) {
+                Some(inner) => Aggregate { variant, ty: inner.clone(), field: AggregateField::Boxed },
+                None => Aggregate { variant, ty: ty.clone(), field: AggregateField::Plain },
+            }])
+        }
+        _ => {
+            let message = format!("Variant `{}` is marked with #[anyhow] but doesn't hold a single field", variant.ident);
+            Err(Error::new_spanned(&variant.ident, message))
+        }
+    }
+}
+
+/// Finds a variant holding `with_type` or a `Box` of it in one of several fields, or in a named field.
+fn find_multi_field_aggregate<'a>(variants: &'a Punctuated<Variant, Comma>, with_type: &Type) -> Option<&'a Variant> {
+    variants.iter().find(|variant| {
+        VariantShape::of(variant).single_tuple_field().is_none() && variant.fields.iter().any(|field| {
+            types_match(&field.ty, with_type) || get_box_inner_type(&field.ty).is_some_and(|inner| types_match(inner, with_type))
+        })
+    })
+}
+
+/// Picks the type of `aggregate_any_of(...)` held by the enum, directly or in a `Box`.
+/// Only one of them may be held, as each would need its own `From` impl.
+fn pick_aggregate(enum_name: &Ident, variants: &Punctuated<Variant, Comma>, any_of: &[Type]) -> Result<Type, Error> {
+    let holds = |ty: &Type| variants.iter().any(|variant| match get_single_field_type(variant) {
+        Some(field_type) => types_match(field_type, ty) || get_box_inner_type(field_type).is_some_and(|inner| types_match(inner, ty)),
+        None => false,
+    });
+
+    let held: Vec<&Type> = any_of.iter().filter(|ty| holds(ty)).collect();
+    let names = |types: &mut dyn Iterator<Item = &Type>| types.map(|ty| format!("`{}`", type_name(ty))).collect::<Vec<_>>().join(", ");
+
+    match held[..] {
+        [aggregate] => Ok(aggregate.clone()),
+        [] => {
+            let message = format!("Could not find a variant with any of the types {} in this enum", names(&mut any_of.iter()));
+            Err(derive_error!(enum_name.span(), message))
+        }
+        _ => {
+            let message = format!("`{}` holds several of the aggregate types {}, only one of them can be converted from", enum_name, names(&mut held.iter().copied()));
+            Err(derive_error!(enum_name.span(), message))
+        }
+    }
+}
+
+/// Picks the aggregate variant `From<anyhow::Error>` converts into among the variants holding the aggregate.
+/// With several of them, it has to be marked with `#[default_anyhow]`.
+fn get_default_aggregate<'a>(enum_name: &Ident, variants: &Punctuated<Variant, Comma>, candidates: Vec<Aggregate<'a>>, with_type: &Type) -> Result<Aggregate<'a>, Error> {
+    let mut marked = Vec::new();
+
+    for variant in variants {
+        if has_flag_attribute(&variant.attrs, "default_anyhow")? {
+            marked.push(variant);
+        }
+    }
+
+    if let [_, duplicate, ..] = marked[..] {
+        let message = format!("Variant `{}` is marked with #[default_anyhow] as well, but only one variant can be the default", duplicate.ident);
+        return Err(Error::new_spanned(&duplicate.ident, message));
+    }
+
+    // The From impl fills the aggregate variant with the value alone, so a variant holding the aggregate next to other fields or in a named field can't be it
+    if let Some(variant) = candidates.is_empty().then(|| find_multi_field_aggregate(variants, with_type)).flatten() {
+        let message = match VariantShape::of(variant).single_field().and_then(|field| field.ident.as_ref()) {
+            Some(field) => format!(
+                "Variant `{}` holds {} in the named field `{}`, but the aggregate variant has to hold it in a tuple field, such as `{}({})`",
+                variant.ident, type_name(with_type), field, variant.ident, type_name(with_type)
+            ),
+            None => format!(
+                "Variant `{}` holds {} next to other fields, but the aggregate variant has to hold it as its single field, such as `{}({})`",
+                variant.ident, type_name(with_type), variant.ident, type_name(with_type)
+            ),
+        };
+        return Err(Error::new_spanned(&variant.fields, message));
+    }
+
+    match (marked.first(), candidates.len()) {
+        (_, 0) => Err(derive_error!(enum_name.span(), format!("Could not find a variant with {} type in this enum", type_name(with_type)))),
+        (Some(default), _) => match candidates.into_iter().find(|candidate| candidate.variant.ident == default.ident) {
+            Some(aggregate) => Ok(aggregate),
+            None => {
+                let message = format!("Variant `{}` is marked with #[default_anyhow] but doesn't hold {}", default.ident, type_name(with_type));
+                Err(Error::new_spanned(&default.ident, message))
+            }
+        },
+        (None, 1) => Ok(candidates.into_iter().next().unwrap()),
+        (None, _) => {
+            let names: Vec<String> = candidates.iter().map(|candidate| format!("`{}`", candidate.variant.ident)).collect();
+            let message = format!(
+                "Variants {} all hold {}, mark the one From<{}> converts into with #[default_anyhow]",
+                names.join(", "), type_name(with_type), type_name(with_type)
+            );
+            Err(Error::new_spanned(&candidates[1].variant.ident, message))
+        }
+    }
+}
+
+/// Structurally compares two types, ignoring a leading `::` on paths.
+/// Spacing never matters since the types are compared as syntax trees.
+fn types_match(left: &Type, right: &Type) -> bool {
+    match (left, right) {
+        (Type::Path(left), Type::Path(right)) => left.qself == right.qself && paths_match(&left.path, &right.path),
+        // `dyn Error + Send + Sync` and `dyn Error + Sync + Send` are the same type, but not `dyn Error`
+        (Type::TraitObject(left), Type::TraitObject(right)) => bounds_match(&left.bounds, &right.bounds),
+        (Type::ImplTrait(left), Type::ImplTrait(right)) => bounds_match(&left.bounds, &right.bounds),
+        (Type::Paren(left), right) | (right, Type::Paren(left)) => types_match(&left.elem, right),
+        _ => left == right,
+    }
+}
+
+/// Compares two paths segment by segment, ignoring a leading `::`.
+fn paths_match(left: &Path, right: &Path) -> bool {
+    left.segments.len() == right.segments.len()
+        && left.segments.iter().zip(&right.segments).all(|(left, right)| {
+            // `r#anyhow` and `anyhow` name the same path segment
+            left.ident.unraw() == right.ident.unraw() && arguments_match(&left.arguments, &right.arguments)
+        })
+}
+
+/// Compares the bounds of trait objects and `impl Trait` in any order, comparing trait paths with `paths_match`.
+fn bounds_match(left: &Punctuated<TypeParamBound, Token![+]>, right: &Punctuated<TypeParamBound, Token![+]>) -> bool {
+    let bound_match = |left: &TypeParamBound, right: &TypeParamBound| match (left, right) {
+        (TypeParamBound::Trait(left), TypeParamBound::Trait(right)) => {
+            left.modifier == right.modifier && left.lifetimes == right.lifetimes && paths_match(&left.path, &right.path)
+        }
+        (left, right) => left == right,
+    };
+
+    left.len() == right.len() && left.iter().all(|bound| right.iter().any(|other| bound_match(bound, other)))
+}
+
+/// Compares the generic arguments of two path segments, comparing type arguments with `types_match`.
+fn arguments_match(left: &PathArguments, right: &PathArguments) -> bool {
+    match (left, right) {
+        (PathArguments::AngleBracketed(left), PathArguments::AngleBracketed(right)) => {
+            left.args.len() == right.args.len() && left.args.iter().zip(&right.args).all(|pair| match pair {
+                (GenericArgument::Type(left), GenericArgument::Type(right)) => types_match(left, right),
+                (left, right) => left == right,
+            })
+        }
+        _ => left == right,
+    }
+}
+
+/// Whether `ty` names the enum itself, as `Self` or by its name with or without generic arguments.
+/// Type aliases of the enum can't be seen through.
+fn is_enum_type(ty: &Type, enum_name: &Ident) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+            let ident = path.path.segments[0].ident.unraw();
+            ident == "Self" || ident == enum_name.unraw()
+        }
+        _ => false,
+    }
+}
+
+/// Renders a type for use in error messages, e.g. `anyhow::Error` rather than `anyhow :: Error`.
+fn type_name(ty: &Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+}
+
+/// Returns the type a `From` impl would be generated for, if the variant has a single field.
+fn get_single_field_type(variant: &Variant) -> Option<&Type> {
+    VariantShape::of(variant).single_field().map(|field| &field.ty)
+}
+
+/// Returns the field a `From` impl fills, together with its position.
+/// This is the only field of the variant, or the one marked with `#[from]` if there are several.
+fn get_source_field(variant: &Variant) -> Result<Option<(usize, &Field)>, Error> {
+    let mut marked = variant.fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.attrs.iter().any(|attr| attr.path().is_ident("from")));
+
+    if let Some((index, field)) = marked.next() {
+        if let Some((_, duplicate)) = marked.next() {
+            let message = format!("Variant `{}` has more than one field marked with #[from]", variant.ident);
+            return Err(Error::new_spanned(duplicate, message));
+        }
+
+        has_flag_attribute(&field.attrs, "from")?;
+        return Ok(Some((index, field)));
+    }
+
+    match variant.fields.len() {
+        0 => Ok(None),
+        1 => Ok(variant.fields.iter().next().map(|field| (0, field))),
+        _ => {
+            let message = format!("Variant `{}` has more than one field but none is marked with #[from]", variant.ident);
+            Err(Error::new_spanned(&variant.ident, message))
+        }
+    }
+}
+
+/// Path naming the variant `variant` of the enum `ty` in a pattern, e.g. `super::Inner::<String>::Anyhow` for `super::Inner<String>`.
+/// The variant is appended as a path segment, with generic arguments turned into a turbofish,
+/// and types wrapped in invisible groups by `macro_rules!` are unwrapped first.
+fn variant_path(ty: &Type, variant: &Ident) -> TokenStream2 {
+    match ty {
+        Type::Group(group) => variant_path(&group.elem, variant),
+        Type::Paren(paren) => variant_path(&paren.elem, variant),
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let mut path = type_path.path.clone();
+
+            for segment in &mut path.segments {
+                if let PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+                    arguments.colon2_token = Some(Default::default());
+                }
+            }
+
+            path.segments.push(variant.clone().into());
+            path.into_token_stream()
+        }
+        _ => quote!(#ty::#variant),
+    }
+}
+
+/// Whether `ty` is a qualified path such as `<Postgres as Backend>::Error`, which a variant can't be appended to in a pattern.
+fn is_qualified_path(ty: &Type) -> bool {
+    match ty {
+        Type::Group(group) => is_qualified_path(&group.elem),
+        Type::Paren(paren) => is_qualified_path(&paren.elem),
+        Type::Path(type_path) => type_path.qself.is_some(),
+        _ => false,
+    }
+}
+
+/// Returns `Inner` if `ty` is `Box<Inner>`.
+fn get_box_inner_type(ty: &Type) -> Option<&Type> {
+    get_pointer_inner_type(ty, "Box")
+}
+
+/// Returns `T` if `ty` is the smart pointer `pointer<T>`, such as `Arc<T>`.
+fn get_pointer_inner_type<'a>(ty: &'a Type, pointer: &str) -> Option<&'a Type> {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                if segment.ident == pointer && arguments.args.len() == 1 {
+                    if let GenericArgument::Type(inner) = &arguments.args[0] {
+                        return Some(inner);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns `Inner` for a `#[boxed]` variant whose field is `Box<Inner>`.
+fn get_boxed_type<'a>(variant: &Variant, ty: &'a Type) -> Result<&'a Type, Error> {
+    if let Some(inner) = get_box_inner_type(ty) {
+        return Ok(inner);
+    }
+
+    let message = format!("Variant `{}` is marked with #[boxed] but its field is not a Box", variant.ident);
+    Err(Error::new_spanned(ty, message))
+}
+
+/// The type the `From` impl of a variant converts from, given the type `ty` of its field.
+/// This is the type inside the pointer for `#[boxed]` and `#[arc]` variants, the element type for `#[from_many]` variants
+/// and `ty` itself otherwise.
+fn get_converted_type<'a>(model: &VariantModel, ty: &'a Type) -> Result<&'a Type, Error> {
+    let variant = model.variant;
+
+    // Combinations are rejected when parsing the variant
+    let (pointer, attribute, container) = match (model.boxed, model.arc, model.from_many) {
+        (true, _, _) => return get_boxed_type(variant, ty),
+        (_, true, _) => ("Arc", "arc", "an Arc"),
+        (_, _, true) => ("Vec", "from_many", "a Vec"),
+        _ => return Ok(ty),
+    };
+
+    match get_pointer_inner_type(ty, pointer) {
+        Some(inner) => Ok(inner),
+        None => {
+            let message = format!("Variant `{}` is marked with #[{}] but its field is not {}", variant.ident, attribute, container);
+            Err(Error::new_spanned(ty, message))
+        }
+    }
+}
+
+/// The `From` impls of a single variant, for its field, the types of `#[from_types(...)]` and `#[from_display(...)]`, and `#[from_ref]`.
+fn generate_variant_impls(options: &Options, enum_name: &Ident, generics: &Generics, model: &VariantModel, variants: &[VariantModel], aggregate: Option<&Aggregate>) -> Result<Vec<TokenStream2>, Error> {
+    // The bounds of #[bound("...")] only apply to this variant's impls
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(model.bounds.iter().cloned());
+    let generics = &generics;
+
+    let mut streams = generate_from_types_impls(options, enum_name, generics, model)?;
+    streams.extend(generate_from_display_impls(options, enum_name, generics, model)?);
+    streams.extend(generate_from_ref_impl(options, enum_name, generics, model)?);
+
+    // Variants holding the aggregate are filled by the aggregate's own From impl, or not at all.
+    // Flattened variants are matched on by name instead of extracting the aggregate.
+    if model.holds_aggregate.is_none() {
+        match model.flatten.is_empty() {
+            true => streams.extend(generate_impl(options, enum_name, generics, model, aggregate)?),
+            false => streams.extend(generate_flatten_impl(options, enum_name, generics, model, variants)?),
+        }
+    }
+
+    // The impls of a variant behind #[cfg(...)] reference its inner type, so they are gated the same way.
+    // Only the attribute form sees such variants, the derive is given the enum with disabled variants removed.
+    let cfgs = cfg_attributes(model.variant);
+    if !cfgs.is_empty() {
+        streams = streams.into_iter().map(|stream| quote! {
+            #(#cfgs)*
+            const _: () = {
+                #stream
+            };
+        }).collect();
+    }
+
+    Ok(streams)
+}
+
+/// The `#[cfg(...)]` attributes gating a variant.
+fn cfg_attributes(variant: &Variant) -> Vec<&Attribute> {
+    variant.attrs.iter().filter(|attr| attr.path().is_ident("cfg")).collect()
+}
+
+/// Generates the deprecated constructor of each `#[deprecated_from("...")]` variant, `from_` and the variant in snake case,
+/// converting like its `From` impl. Rust ignores `#[deprecated]` on trait impls, so callers are only warned through the constructor.
+fn generate_deprecated_ctors(options: &Options, vis: &Visibility, enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> Result<Option<TokenStream2>, Error> {
+    let from = options.convert_from();
+    let mut ctors = Vec::new();
+
+    for model in variants {
+        let variant = model.variant;
+
+        let attr = match variant.attrs.iter().find(|attr| attr.path().is_ident("deprecated_from")) {
+            Some(attr) => attr,
+            None => continue,
+        };
+
+        let note: LitStr = attr.parse_args()?;
+
+        let source = match model.skip_from || model.try_from || model.holds_aggregate.is_some() {
+            true => None,
+            false => get_source_field(variant)?,
+        };
+
+        let ty = match source {
+            Some((_, field)) => get_converted_type(model, &field.ty)?,
+            None => {
+                let message = format!("Variant `{}` is marked with #[deprecated_from], but gets no From impl to deprecate", variant.ident);
+                return Err(Error::new_spanned(attr, message));
+            }
+        };
+
+        let name = format_ident!("from_{}", snake_case(&variant.ident));
+        let cfgs = cfg_attributes(variant);
+
+        ctors.push(quote! {
+            #(#cfgs)*
+            #[deprecated = #note]
+            #vis fn #name(value: #ty) -> Self {
+                <Self as #from<#ty>>::from(value)
+            }
+        });
+    }
+
+    if ctors.is_empty() {
+        return Ok(None);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote! {
+        #[allow(clippy::all)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(#ctors)*
+        }
+    }))
+}
+
+/// Errors on the second of two variants wrapping the same type, including the types listed in `#[from_types(...)]` and `#[from_display(...)]`,
+/// and on the types of `catch_all_into_anyhow(...)` that a variant already converts from.
+fn check_duplicate_types<'a>(variants: &'a [VariantModel], aggregate: Option<&Aggregate>, catch_all: &[Type]) -> Result<(), Error> {
+    // Each type is recorded with the variant converting from it, and the attribute listing it unless the variant wraps it
+    let mut seen: Vec<(&Ident, &'a Type, Option<&str>)> = Vec::new();
+    let mut errors = Vec::new();
+
+    for model in variants {
+        let variant = model.variant;
+
+        // Gated variants may exclude each other, which can't be told from their #[cfg(...)]
+        if !cfg_attributes(variant).is_empty() {
+            continue;
+        }
+
+        // Only the default aggregate variant has a From impl among the variants holding the aggregate
+        let secondary_aggregate = model.holds_aggregate.is_some() && aggregate.is_some_and(|aggregate| aggregate.variant.ident != variant.ident);
+
+        let variant_inner_type = match model.skip_from || model.try_from || secondary_aggregate {
+            true => None,
+            false => match get_source_field(variant)? {
+                Some((_, field)) => Some(get_converted_type(model, &field.ty)?),
+                None => None,
+            },
+        };
+
+        if let Some(variant_inner_type) = variant_inner_type {
+            if let Some((first_variant, _, listed_in)) = seen.iter().find(|(_, ty, _)| types_match(ty, variant_inner_type)) {
+                let message = match listed_in {
+                    Some(attribute) => format!(
+                        "Variant `{}` lists `{}` in #[{}], but `{}` already converts from it",
+                        first_variant, type_name(variant_inner_type), attribute, variant.ident
+                    ),
+                    None => format!(
+                        "Variants `{}` and `{}` both wrap `{}`, which would generate conflicting From impls",
+                        first_variant, variant.ident, type_name(variant_inner_type)
+                    ),
+                };
+                errors.push(Error::new_spanned(&variant.ident, message));
+            }
+
+            seen.push((&variant.ident, variant_inner_type, None));
+        }
+
+        let listed = model.from_types.iter().map(|ty| ("from_types", ty)).chain(model.from_display.iter().map(|ty| ("from_display", ty)));
+        for (attribute, from_type) in listed {
+            if let Some((first_variant, _, _)) = seen.iter().find(|(_, ty, _)| types_match(ty, from_type)) {
+                let message = format!(
+                    "Variant `{}` lists `{}` in #[{}], but `{}` already converts from it",
+                    variant.ident, type_name(from_type), attribute, first_variant
+                );
+                errors.push(Error::new_spanned(from_type, message));
+            }
+
+            seen.push((&variant.ident, from_type, Some(attribute)));
+        }
+    }
+
+    for from_type in catch_all {
+        if let Some((variant, _, _)) = seen.iter().find(|(_, ty, _)| types_match(ty, from_type)) {
+            let message = format!("`{}` is listed in catch_all_into_anyhow, but `{}` already converts from it", type_name(from_type), variant);
+            errors.push(Error::new_spanned(from_type, message));
+        }
+    }
+
+    combine_errors(errors)
+}
+
+/// Errors on every variant that gets no `From` impl although `strict` requires one, that is without a field to convert from
+/// or with several fields and none marked with `#[from]`. Variants holding the aggregate and `skip_from` or `try_from` variants are exempt.
+fn check_strict(variants: &[VariantModel]) -> Result<(), Error> {
+    let errors = variants.iter().filter(|model| model.holds_aggregate.is_none() && !model.skip_from && !model.try_from).filter_map(|model| {
+        let variant = model.variant;
+        let marked = variant.fields.iter().any(|field| field.attrs.iter().any(|attr| attr.path().is_ident("from")));
+
+        let reason = match variant.fields.len() {
+            0 => "has no field to convert from",
+            1 => return None,
+            _ if marked => return None,
+            _ => "has several fields and none is marked with #[from]",
+        };
+
+        let message = format!("Variant `{}` gets no From impl as it {}, but error_enum(strict) requires one unless it is marked with #[skip_from]", variant.ident, reason);
+        Some(Error::new_spanned(&variant.ident, message))
+    }).collect();
+
+    combine_errors(errors)
+}
+
+/// Errors on variants gated with `#[cfg(...)]` if an impl naming every variant is generated,
+/// as the impl would still name them where they are disabled.
+fn check_cfg_variants(options: &Options, variants: &[VariantModel]) -> Result<(), Error> {
+    let has_display = variants.iter().any(|model| model.transparent || model.variant.attrs.iter().any(|attr| attr.path().is_ident("error")));
+
+    let whole_enum = [
+        (options.kind, "error_enum(kind)"),
+        (options.std_error, "error_enum(std_error)"),
+        (options.debug, "error_enum(debug)"),
+        (options.predicates, "error_enum(predicates)"),
+        (options.accessors, "error_enum(accessors)"),
+        (options.into_inner, "error_enum(into_inner)"),
+        (options.const_ctors, "error_enum(const_ctors)"),
+        (options.into_response, "error_enum(into_response)"),
+        (options.serialize, "error_enum(serialize)"),
+        (options.into_aggregate, "error_enum(into_aggregate)"),
+        (options.into_anyhow_method, "error_enum(into_anyhow_method)"),
+        (options.downcast_from_anyhow, "error_enum(downcast_from_anyhow)"),
+        (has_display, "the Display impl generated from #[error]"),
+    ];
+
+    let errors = variants.iter().filter(|model| !cfg_attributes(model.variant).is_empty()).filter_map(|model| {
+        let generator = match whole_enum.iter().find(|(enabled, _)| *enabled) {
+            Some((_, name)) => *name,
+            None if model.try_from => "#[try_from]",
+            None => return None,
+        };
+
+        let message = format!("Variant `{}` is gated with #[cfg(...)], which {} doesn't support yet, as it would name the variant where it is disabled", model.variant.ident, generator);
+        Some(Error::new_spanned(&model.variant.ident, message))
+    }).collect();
+
+    combine_errors(errors)
+}
+
+/// Checks whether a flag attribute such as `#[without_anyhow]` is present.
+/// Flags take no arguments, so `#[without_anyhow(...)]` is rejected.
+fn has_flag_attribute(attrs: &[Attribute], name: &str) -> Result<bool, Error> {
+    match attrs.iter().find(|attr| attr.path().is_ident(name)) {
+        Some(attr) => attr.meta.require_path_only().map(|_| true),
+        None => Ok(false),
+    }
+}
+
+/// Returns the string of a `#[name = "..."]` attribute, if present.
+fn get_string_attribute(attrs: &[Attribute], name: &str) -> Result<Option<LitStr>, Error> {
+    let attr = match attrs.iter().find(|attr| attr.path().is_ident(name)) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    match &attr.meta.require_name_value()?.value {
+        Expr::Lit(ExprLit { lit: Lit::Str(value), .. }) => Ok(Some(value.clone())),
+        value => Err(Error::new_spanned(value, format!("expected a string, as in #[{} = \"...\"]", name))),
+    }
+}
+
+fn generate_impl(options: &Options, enum_name: &Ident, generics: &Generics, model: &VariantModel, aggregate: Option<&Aggregate>) -> Result<Option<TokenStream2>, Error> {
+    let variant = model.variant;
+    let anyhow_variant = aggregate.map(|aggregate| &aggregate.variant.ident);
+
+    // Skipped variants get no conversion, regardless of `without_anyhow`
+    if model.skip_from {
+        return Ok(None);
+    }
+
+    // Variants converted by downcasting the aggregate get no From impl
+    if model.try_from {
+        if anyhow_variant.is_none() {
+            let message = format!("Variant `{}` is marked with #[try_from], which needs an aggregate variant to downcast", variant.ident);
+            return Err(Error::new_spanned(&variant.ident, message));
+        }
+
+        return Ok(None);
+    }
+
+    let variant_name = &variant.ident;
+
+    let (source_index, field) = match get_source_field(variant)? {
+        Some(source) => source,
+        None => return Ok(None),
+    };
+
+    // Boxed and arc variants convert from the type inside the pointer, from_many variants from a single element
+    let variant_inner_type = get_converted_type(model, &field.ty)?;
+
+    // `From<MyError> for MyError` would conflict with the reflexive `From<T> for T` of the standard library
+    if is_enum_type(variant_inner_type, enum_name) {
+        let message = format!(
+            "Variant `{}` holds `{}` itself, whose From impl would conflict with the blanket `impl From<T> for T`, mark it with #[skip_from]",
+            variant_name, enum_name
+        );
+        return Err(Error::new_spanned(variant_inner_type, message));
+    }
+
+    // A #[build_with] closure replaces whatever the value would otherwise be turned into
+    if let Some(closure) = &model.build_with {
+        let BuilderClosure { movability, argument, ty, body } = closure;
+        let ty = ty.as_ref().unwrap_or(variant_inner_type);
+        let from = options.convert_from();
+        let trace = options.trace_conversion(variant_inner_type);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let span = Span::call_site().located_at(variant_name.span());
+
+        return Ok(Some(quote_spanned! {span=>
+            #[automatically_derived]
+            #[allow(clippy::all)]
+            impl #impl_generics #from<#variant_inner_type> for #enum_name #ty_generics #where_clause {
+                fn from(value: #variant_inner_type) -> Self {
+                    #trace
+                    (#movability |#argument: #ty| -> Self { #body })(value)
+                }
+            }
+        }));
+    }
+
+    let value = match (model.boxed, model.arc, model.from_many) {
+        (true, _, _) => {
+            let boxed_path = options.box_type();
+            quote!(#boxed_path::new(value))
+        }
+        (_, true, _) => {
+            let arc_path = options.arc_type();
+            quote!(#arc_path::new(value))
+        }
+        (_, _, true) => {
+            let vec_macro = options.vec_macro();
+            quote!(#vec_macro[value])
+        }
+        _ => quote!(value),
+    };
+
+    // The expression constructing our variant from `value`, defaulting all other fields
+    let variant_value = construct_variant(variant, source_index, &value);
+
+    let without_anyhow = model.without_anyhow;
+
+    // Values of a variant with #[context] are routed into the aggregate as a whole, carrying the message
+    let context = variant.attrs.iter().find(|attr| attr.path().is_ident("context"));
+    let variant_value = match context {
+        Some(attr) => {
+            let context: LitStr = attr.parse_args()?;
+
+            if options.opaque_aggregate() {
+                let kind = match options.trait_object_aggregate() {
+                    true => "a boxed trait object aggregate",
+                    false => "an aggregate of aggregate_kind = \"struct\"",
+                };
+                let message = format!("Variant `{}` is marked with #[context], but {} can't carry context", variant_name, kind);
+                return Err(Error::new_spanned(attr, message));
+            }
+
+            let aggregate = match aggregate {
+                Some(aggregate) => aggregate,
+                None => {
+                    let message = format!("Variant `{}` is marked with #[context], which needs an aggregate variant", variant_name);
+                    return Err(Error::new_spanned(attr, message));
+                }
+            };
+
+            let anyhow_variant = &aggregate.variant.ident;
+            let anyhow_type = &aggregate.ty;
+            let into = match aggregate.converted() {
+                true => quote!(.into()),
+                false => quote!(),
+            };
+            // `wrap_err` comes from eyre's `WrapErr` trait, which the `eyre` feature opts into
+            if types_match(anyhow_type, &parse_quote!(eyre::Report)) && !cfg!(feature = "eyre") {
+                let message = format!("Variant `{}` is marked with #[context], which requires the `eyre` feature of error-conversion-macro for an eyre::Report aggregate", variant_name);
+                return Err(Error::new_spanned(attr, message));
+            }
+
+            let context_method = options.context_method();
+            quote!(#anyhow_variant(<#anyhow_type>::new(value).#context_method(#context)#into))
+        }
+        None => variant_value,
+    };
+
+    // Values of a variant with #[into_anyhow] are converted into the aggregate as a whole, without matching on them
+    let into_anyhow = variant.attrs.iter().find(|attr| attr.path().is_ident("into_anyhow"));
+    let variant_value = match into_anyhow {
+        Some(attr) => {
+            attr.meta.require_path_only()?;
+
+            let aggregate = match aggregate {
+                Some(aggregate) => aggregate,
+                None => {
+                    let message = format!("Variant `{}` is marked with #[into_anyhow], which needs an aggregate variant", variant_name);
+                    return Err(Error::new_spanned(attr, message));
+                }
+            };
+
+            let anyhow_variant = &aggregate.variant.ident;
+            let anyhow_type = &aggregate.ty;
+            match aggregate.converted() {
+                true => quote!(#anyhow_variant(<#anyhow_type>::from(value).into())),
+                false => quote!(#anyhow_variant(value.into())),
+            }
+        }
+        None => variant_value,
+    };
+
+    // Values of a variant with #[via(B)] are converted into B first, and from there into the aggregate
+    let via = variant.attrs.iter().find(|attr| attr.path().is_ident("via"));
+    let variant_value = match via {
+        Some(attr) => {
+            let via: Type = attr.parse_args()?;
+
+            let aggregate = match aggregate {
+                Some(aggregate) => aggregate,
+                None => {
+                    let message = format!("Variant `{}` is marked with #[via], which needs an aggregate variant", variant_name);
+                    return Err(Error::new_spanned(attr, message));
+                }
+            };
+
+            let anyhow_variant = &aggregate.variant.ident;
+            let anyhow_type = &aggregate.ty;
+            match aggregate.converted() {
+                true => quote!(#anyhow_variant(<#anyhow_type>::from(<#via>::from(value)).into())),
+                false => quote!(#anyhow_variant(<#via>::from(value).into())),
+            }
+        }
+        None => variant_value,
+    };
+
+    let from = options.convert_from();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The inner type's variant holding anyhow::Error, named like ours unless overridden
+    let inner_anyhow_variant = match get_string_attribute(&variant.attrs, "anyhow_variant")? {
+        Some(name) => Some(name.parse::<Ident>()?),
+        None => anyhow_variant.cloned(),
+    };
+
+    let trace = options.trace_conversion(variant_inner_type);
+
+    // The path of the inner type's variant holding the aggregate, with a turbofish for generic inner enums
+    let inner_anyhow_path = inner_anyhow_variant.map(|inner_anyhow_variant| variant_path(variant_inner_type, &inner_anyhow_variant));
+
+    // `wrap` only drops the extraction, the variant is still treated as an error elsewhere.
+    // A boxed trait object or struct aggregate can't be matched on, so its variants are always wrapped, as are tuples.
+    let tuple = matches!(variant_inner_type, Type::Tuple(_));
+    let wrapped = without_anyhow || model.wrap || context.is_some() || into_anyhow.is_some() || via.is_some() || options.opaque_aggregate() || tuple;
+
+    // Inner enums hold the aggregate itself, which only has to be converted if ours is boxed or a newtype,
+    // unless a #[map_anyhow] function turns it into our field
+    let map_anyhow = variant.attrs.iter().find(|attr| attr.path().is_ident("map_anyhow"));
+    let extracted = match (map_anyhow, aggregate) {
+        (Some(attr), _) => {
+            if wrapped {
+                let message = format!("Variant `{}` is marked with #[map_anyhow], but is wrapped without extracting anything to map", variant_name);
+                return Err(Error::new_spanned(attr, message));
+            }
+
+            let function: Path = attr.parse_args()?;
+            quote!(#function(e))
+        }
+        (None, Some(aggregate)) if aggregate.converted() => quote!(e.into()),
+        (None, _) => quote!(e),
+    };
+
+    // A reference can't be matched on to move the aggregate out, so it has to be wrapped explicitly
+    if let (false, Some(_), Type::Reference(_)) = (wrapped, anyhow_variant, variant_inner_type) {
+        let message = format!(
+            "Variant `{}` holds a reference, which can't be matched on to extract an {}, mark it with #[without_anyhow] or #[wrap]",
+            variant_name, type_name(&options.aggregate)
+        );
+        return Err(Error::new_spanned(variant_inner_type, message));
+    }
+
+    // Rust doesn't accept variants of qualified associated types in patterns either
+    if !wrapped && anyhow_variant.is_some() && is_qualified_path(variant_inner_type) {
+        let message = format!(
+            "Variant `{}` holds a qualified associated type, whose variants can't be matched on to extract an {}, mark it with #[without_anyhow] or #[wrap]",
+            variant_name, type_name(&options.aggregate)
+        );
+        return Err(Error::new_spanned(variant_inner_type, message));
+    }
+
+    // The impls are located at the variant, so that going to the definition of a conversion lands on it.
+    // They keep resolving names like the rest of the generated code, as the variant name may come from another macro.
+    let span = Span::call_site().located_at(variant_name.span());
+
+    let generated = match (wrapped, anyhow_variant, inner_anyhow_path) {
+        // Generate the full From implementation, extracting anyhow::Error from the variant type.
+        (false, Some(anyhow_variant), Some(inner_anyhow_path)) => quote_spanned! {span=>
+                    #[automatically_derived]
+                    #[allow(clippy::all)]
+                    impl #impl_generics #from<#variant_inner_type> for #enum_name #ty_generics #where_clause {
+                        fn from(value: #variant_inner_type) -> Self {
+                            #trace
+                            match value {
+                                #inner_anyhow_path(e) => #enum_name::#anyhow_variant(#extracted),
+                                // Inner enums holding nothing but the aggregate leave the wildcard unreachable
+                                #[allow(unreachable_patterns)]
+                                _ => #enum_name::#variant_value,
+                            }
+                        }
+                    }
+                },
+
+        // Don't extract anyhow::Error from the variant type, instead just wrap the type in our enum.
+        _ => quote_spanned! {span=>
+                    #[automatically_derived]
+                    #[allow(clippy::all)]
+                    impl #impl_generics #from<#variant_inner_type> for #enum_name #ty_generics #where_clause {
+                        fn from(value: #variant_inner_type) -> Self {
+                            #trace
+                            Self::#variant_value
+                        }
+                    }
+                }
+    };
+
+    // An arc variant also takes an already shared value as is, and a from_many variant a whole Vec
+    let shared = match model.arc || model.from_many {
+        true => {
+            let shared_type = &field.ty;
+            let shared_value = construct_variant(variant, source_index, &quote!(value));
+            let trace = options.trace_conversion(shared_type);
+            quote_spanned! {span=>
+                #[automatically_derived]
+                #[allow(clippy::all)]
+                impl #impl_generics #from<#shared_type> for #enum_name #ty_generics #where_clause {
+                    fn from(value: #shared_type) -> Self {
+                        #trace
+                        Self::#shared_value
+                    }
+                }
+            }
+        }
+        false => quote!(),
+    };
+
+    // A from_many variant also collects an iterator of elements
+    let collected = match model.from_many {
+        true => {
+            let std = options.std_crate();
+            let collected_value = construct_variant(variant, source_index, &quote!(#std::iter::Iterator::collect(#std::iter::IntoIterator::into_iter(iter))));
+            quote_spanned! {span=>
+                #[automatically_derived]
+                #[allow(clippy::all)]
+                impl #impl_generics #std::iter::FromIterator<#variant_inner_type> for #enum_name #ty_generics #where_clause {
+                    fn from_iter<I: #std::iter::IntoIterator<Item = #variant_inner_type>>(iter: I) -> Self {
+                        Self::#collected_value
+                    }
+                }
+            }
+        }
+        false => quote!(),
+    };
+
+    Ok(Some(quote! {
+        #generated
+        #shared
+        #collected
+    }))
+}
+
+/// Expression constructing `variant` with `value` in the field at `source_index` and all other fields defaulted.
+/// The defaults are spanned at the field types, so that a type without `Default` is reported at its field.
+fn construct_variant(variant: &Variant, source_index: usize, value: &TokenStream2) -> TokenStream2 {
+    let variant_name = &variant.ident;
+
+    let field_values = variant.fields.iter().enumerate().map(|(index, field)| {
+        let field_type = &field.ty;
+        let field_value = match index == source_index {
+            true => value.clone(),
+            false => quote_spanned!(field_type.span()=> <#field_type as Default>::default()),
+        };
+
+        match &field.ident {
+            Some(field_name) => quote!(#field_name: #field_value),
+            None => field_value,
+        }
+    });
+
+    match VariantShape::of(variant) {
+        VariantShape::Named(_) => quote!(#variant_name { #(#field_values),* }),
+        VariantShape::Tuple(_) => quote!(#variant_name(#(#field_values),*)),
+        VariantShape::Unit => quote!(#variant_name),
+    }
+}
+
+/// Generates a `From` impl for every type listed in the `#[from_types(...)]` of a variant,
+/// wrapping the listed type with `anyhow::Error::new` for variants holding the aggregate and converting it with `.into()` otherwise.
+fn generate_from_types_impls(options: &Options, enum_name: &Ident, generics: &Generics, model: &VariantModel) -> Result<Vec<TokenStream2>, Error> {
+    let variant = model.variant;
+    let from_types = &model.from_types;
+
+    if from_types.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let source_index = match get_source_field(variant)? {
+        Some((source_index, _)) => source_index,
+        None => {
+            let message = format!("Variant `{}` is marked with #[from_types], but has no field to convert into", variant.ident);
+            return Err(Error::new_spanned(&variant.ident, message));
+        }
+    };
+
+    // Errors routed into the aggregate are wrapped with `new`, keeping their source chain and backtrace
+    let aggregate = &options.aggregate;
+    let value = match model.holds_aggregate {
+        Some(AggregateField::Plain) if !options.opaque_aggregate() => quote!(<#aggregate>::new(value)),
+        Some(AggregateField::Boxed | AggregateField::Newtype) if !options.opaque_aggregate() => quote!(<#aggregate>::new(value).into()),
+        _ => quote!(value.into()),
+    };
+    let variant_value = construct_variant(variant, source_index, &value);
+    let from = options.convert_from();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(from_types.iter().map(|from_type| {
+        let trace = options.trace_conversion(from_type);
+
+        quote! {
+            #[automatically_derived]
+            #[allow(clippy::all)]
+            impl #impl_generics #from<#from_type> for #enum_name #ty_generics #where_clause {
+                fn from(value: #from_type) -> Self {
+                    #trace
+                    Self::#variant_value
+                }
+            }
+        }
+    }).collect())
+}
+
+/// Generates a `From` impl for every type listed in `catch_all_into_anyhow(...)`, converting it into the aggregate variant with `.into()`.
+fn generate_catch_all_impls(options: &Options, enum_name: &Ident, generics: &Generics, aggregate: &Aggregate) -> Vec<TokenStream2> {
+    let anyhow_variant = &aggregate.variant.ident;
+    let anyhow_type = &aggregate.ty;
+    let value = match aggregate.converted() {
+        true => quote!(<#anyhow_type>::from(value).into()),
+        false => quote!(value.into()),
+    };
+    let from = options.convert_from();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    options.catch_all_into_anyhow.iter().map(|from_type| {
+        let trace = options.trace_conversion(from_type);
+
+        quote! {
+            #[automatically_derived]
+            #[allow(clippy::all)]
+            impl #impl_generics #from<#from_type> for #enum_name #ty_generics #where_clause {
+                fn from(value: #from_type) -> Self {
+                    #trace
+                    #enum_name::#anyhow_variant(#value)
+                }
+            }
+        }
+    }).collect()
+}
+
+/// Generates a `From` impl for every type listed in the `#[from_display(...)]` of a variant,
+/// storing the `Display` of the value in the variant's `String` field.
+fn generate_from_display_impls(options: &Options, enum_name: &Ident, generics: &Generics, model: &VariantModel) -> Result<Vec<TokenStream2>, Error> {
+    let variant = model.variant;
+
+    if model.from_display.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let source_index = match get_source_field(variant)? {
+        Some((source_index, _)) => source_index,
+        None => {
+            let message = format!("Variant `{}` is marked with #[from_display], but has no field to store the message in", variant.ident);
+            return Err(Error::new_spanned(&variant.ident, message));
+        }
+    };
+
+    let to_string = match options.no_std {
+        true => quote!(alloc::string::ToString::to_string),
+        false => quote!(std::string::ToString::to_string),
+    };
+    let variant_value = construct_variant(variant, source_index, &quote!(#to_string(&value)));
+    let from = options.convert_from();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(model.from_display.iter().map(|from_type| {
+        let trace = options.trace_conversion(from_type);
+
+        quote! {
+            #[automatically_derived]
+            #[allow(clippy::all)]
+            impl #impl_generics #from<#from_type> for #enum_name #ty_generics #where_clause {
+                fn from(value: #from_type) -> Self {
+                    #trace
+                    Self::#variant_value
+                }
+            }
+        }
+    }).collect())
+}
+
+/// Generates `From<&Inner>` for a `#[from_ref]` variant, cloning the reference and converting like `From<Inner>`.
+fn generate_from_ref_impl(options: &Options, enum_name: &Ident, generics: &Generics, model: &VariantModel) -> Result<Option<TokenStream2>, Error> {
+    let variant = model.variant;
+
+    if !model.from_ref {
+        return Ok(None);
+    }
+
+    if model.try_from {
+        let message = format!("Variant `{}` is marked with #[from_ref] and #[try_from], but has no From impl to route through", variant.ident);
+        return Err(Error::new_spanned(&variant.ident, message));
+    }
+
+    let variant_inner_type = match get_source_field(variant)? {
+        Some((_, field)) => get_converted_type(model, &field.ty)?,
+        None => {
+            let message = format!("Variant `{}` is marked with #[from_ref], but has no field to convert into", variant.ident);
+            return Err(Error::new_spanned(&variant.ident, message));
+        }
+    };
+
+    let from = options.convert_from();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics #from<&#variant_inner_type> for #enum_name #ty_generics #where_clause {
+            fn from(value: &#variant_inner_type) -> Self {
+                <Self as #from<#variant_inner_type>>::from(Clone::clone(value))
+            }
+        }
+    }))
+}
+
+/// Returns the types listed in an attribute such as `#[from_types(...)]`, if present.
+fn get_type_list(variant: &Variant, name: &str) -> Result<Vec<Type>, Error> {
+    match variant.attrs.iter().find(|attr| attr.path().is_ident(name)) {
+        Some(attr) => Ok(attr.parse_args_with(Punctuated::<Type, Comma>::parse_terminated)?.into_iter().collect()),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn get_flatten_variants(variant: &Variant) -> Result<Vec<Ident>, Error> {
+    match variant.attrs.iter().find(|attr| attr.path().is_ident("flatten")) {
+        Some(attr) => Ok(attr.parse_args_with(Punctuated::<Ident, Comma>::parse_terminated)?.into_iter().collect()),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// The closure of `#[build_with(...)]`, such as `|e| MyError::Other(wrap(e))` or `move |e: Inner| ...`.
+/// Parsed by hand, as syn only parses closures with its "full" feature: beyond the single argument, the body is kept as is.
+/// An argument without a type is given the inner type, as a closure called in place can't infer it from the body.
+struct BuilderClosure {
+    movability: Option<Token![move]>,
+    argument: TokenStream2,
+    ty: Option<Type>,
+    body: TokenStream2,
+}
+
+impl Parse for BuilderClosure {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let movability: Option<Token![move]> = input.parse()?;
+
+        if input.peek(Token![||]) {
+            return Err(input.error("build_with expects a closure taking the inner value as its single argument"));
+        }
+
+        input.parse::<Token![|]>()?;
+
+        let argument = match input.parse::<Option<Token![_]>>()? {
+            Some(underscore) => quote!(#underscore),
+            None => input.parse::<Ident>()?.into_token_stream(),
+        };
+
+        let ty = match input.parse::<Option<Token![:]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+
+        if !input.peek(Token![|]) {
+            return Err(input.error("build_with expects a closure taking the inner value as its single argument"));
+        }
+
+        input.parse::<Token![|]>()?;
+
+        if input.is_empty() {
+            return Err(input.error("build_with expects a closure with a body"));
+        }
+
+        Ok(BuilderClosure { movability, argument, ty, body: input.parse()? })
+    }
+}
+
+/// Returns the where-clause predicates of `#[bound("...")]`, if present.
+fn get_bounds(variant: &Variant) -> Result<Vec<WherePredicate>, Error> {
+    match variant.attrs.iter().find(|attr| attr.path().is_ident("bound")) {
+        Some(attr) => {
+            let bounds: LitStr = attr.parse_args()?;
+            Ok(bounds.parse_with(Punctuated::<WherePredicate, Comma>::parse_terminated)?.into_iter().collect())
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+fn get_builder_closure(variant: &Variant) -> Result<Option<BuilderClosure>, Error> {
+    match variant.attrs.iter().find(|attr| attr.path().is_ident("build_with")) {
+        Some(attr) => Ok(Some(attr.parse_args()?)),
+        None => Ok(None),
+    }
+}
+
+/// `path` followed by the fields of `variant` bound to variables, usable both as pattern and as expression.
+fn fields_pattern(path: TokenStream2, variant: &Variant) -> TokenStream2 {
+    match VariantShape::of(variant) {
+        VariantShape::Tuple(fields) => {
+            let bindings = (0..fields.len()).map(|index| format_ident!("field_{}", index));
+            quote!(#path(#(#bindings),*))
+        }
+        VariantShape::Named(fields) => {
+            let names = fields.iter().map(|field| &field.ident);
+            quote!(#path { #(#names),* })
+        }
+        VariantShape::Unit => path,
+    }
+}
+
+/// Generates `From<Inner>` for a `#[flatten(...)]` variant, lifting the listed variants of the inner enum into ours.
+/// The inner variants have to be shaped like ours, the ones not listed are wrapped in the variant itself.
+fn generate_flatten_impl(options: &Options, enum_name: &Ident, generics: &Generics, model: &VariantModel, variants: &[VariantModel]) -> Result<Option<TokenStream2>, Error> {
+    let variant = model.variant;
+
+    if model.flatten.is_empty() || model.skip_from {
+        return Ok(None);
+    }
+
+    let inner_type = match get_single_field_type(variant) {
+        Some(inner_type) => inner_type,
+        None => {
+            let message = format!("Variant `{}` is marked with #[flatten], but has no single field holding the enum to flatten", variant.ident);
+            return Err(Error::new_spanned(&variant.ident, message));
+        }
+    };
+
+    if is_qualified_path(inner_type) {
+        let message = format!("Variant `{}` is marked with #[flatten], but holds a qualified associated type, whose variants can't be matched on", variant.ident);
+        return Err(Error::new_spanned(inner_type, message));
+    }
+
+    let targets: Vec<Option<&Variant>> = model.flatten.iter()
+        .map(|name| variants.iter().map(|model| model.variant).find(|variant| variant.ident.unraw() == name.unraw()))
+        .collect();
+
+    let missing: Vec<String> = model.flatten.iter()
+        .zip(&targets)
+        .filter(|(_, target)| target.is_none())
+        .map(|(name, _)| format!("`{}`", name))
+        .collect();
+
+    if !missing.is_empty() {
+        let message = format!(
+            "Variant `{}` flattens {}, which {} no variant of the same name in `{}`",
+            variant.ident, missing.join(", "), if missing.len() == 1 { "has" } else { "have" }, enum_name
+        );
+        return Err(Error::new_spanned(variant.attrs.iter().find(|attr| attr.path().is_ident("flatten")), message));
+    }
+
+    let arms = model.flatten.iter().zip(targets.into_iter().flatten()).map(|(name, target)| {
+        let target_name = &target.ident;
+        let pattern = fields_pattern(variant_path(inner_type, name), target);
+        let value = fields_pattern(quote!(#enum_name::#target_name), target);
+        quote!(#pattern => #value,)
+    });
+
+    let binding = Ident::new("inner", Span::call_site());
+    let nested = single_field_pattern(enum_name, variant, &binding);
+    let trace = options.trace_conversion(inner_type);
+    let from = options.convert_from();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics #from<#inner_type> for #enum_name #ty_generics #where_clause {
+            fn from(value: #inner_type) -> Self {
+                #trace
+                match value {
+                    #(#arms)*
+                    // Required for #[non_exhaustive] inner enums of other crates, even with every variant listed
+                    #[allow(unreachable_patterns)]
+                    #binding => #nested,
+                }
+            }
+        }
+    }))
+}
+
+/// Pattern matching `variant` and binding its single field to `binding`, if it has exactly one field.
+/// Also usable as the expression constructing the variant from `binding`.
+fn single_field_pattern(enum_name: &Ident, variant: &Variant, binding: &Ident) -> Option<TokenStream2> {
+    let variant_name = &variant.ident;
+
+    let shape = VariantShape::of(variant);
+
+    match (shape, shape.single_field()) {
+        (VariantShape::Tuple(_), Some(_)) => Some(quote!(#enum_name::#variant_name(#binding))),
+        (VariantShape::Named(_), Some(field)) => {
+            let field_name = &field.ident;
+            Some(quote!(#enum_name::#variant_name { #field_name: #binding }))
+        }
+        _ => None,
+    }
+}
+
+/// Pattern matching `variant` while ignoring its fields.
+fn wildcard_pattern(enum_name: &Ident, variant: &Variant) -> TokenStream2 {
+    let variant_name = &variant.ident;
+
+    match VariantShape::of(variant) {
+        VariantShape::Tuple(_) => quote!(#enum_name::#variant_name(..)),
+        VariantShape::Named(_) => quote!(#enum_name::#variant_name { .. }),
+        VariantShape::Unit => quote!(#enum_name::#variant_name),
+    }
+}
+
+fn generate_std_error_impl(options: &Options, enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> Result<TokenStream2, Error> {
+    let std = options.std_crate();
+    let binding = Ident::new("source", Span::call_site());
+    let fallback = options.fallback_arm();
+    let mut arms = Vec::new();
+
+    for model in variants {
+        let variant = model.variant;
+        let pattern = single_field_pattern(enum_name, variant, &binding);
+
+        let inner_error = match &pattern {
+            // A boxed trait object already is the error
+            Some(_) if model.holds_aggregate == Some(AggregateField::Plain) && options.trait_object_aggregate() => Some(quote!(&**#binding)),
+            // The aggregate derefs to the error it holds
+            Some(_) if model.holds_aggregate.is_some() => {
+                let aggregate_value = match model.holds_aggregate {
+                    Some(AggregateField::Plain) => quote!(#binding),
+                    _ => quote!(&**#binding),
+                };
+                Some(quote!(AsRef::<dyn #std::error::Error + 'static>::as_ref(#aggregate_value)))
+            }
+            Some(_) if !model.without_anyhow || model.transparent => Some(quote!(#binding)),
+            _ => None,
+        };
+
+        let arm = match (pattern, inner_error) {
+            // A transparent variant is indistinguishable from its inner error, so it has the inner error's source
+            (Some(pattern), Some(inner_error)) if model.transparent => quote! {
+                #pattern => #std::error::Error::source(#inner_error),
+            },
+            (Some(pattern), Some(inner_error)) => quote! {
+                #pattern => Some(#inner_error),
+            },
+            _ => {
+                let pattern = wildcard_pattern(enum_name, variant);
+                quote!(#pattern => None,)
+            }
+        };
+
+        arms.push(arm);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics #std::error::Error for #enum_name #ty_generics #where_clause {
+            fn source(&self) -> #std::option::Option<&(dyn #std::error::Error + 'static)> {
+                match self {
+                    #(#arms)*
+                    #fallback
+                }
+            }
+        }
+    })
+}
+
+fn generate_display_impl(options: &Options, enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> Result<Option<TokenStream2>, Error> {
+    if !variants.iter().any(|model| model.transparent || model.variant.attrs.iter().any(|attr| attr.path().is_ident("error"))) {
+        return Ok(None);
+    }
+
+    let variants = variants.iter().map(|model| model.variant);
+
+    let std = options.std_crate();
+    let fallback = options.fallback_arm();
+    let mut arms = Vec::new();
+
+    for variant in variants {
+        let arm = match variant.attrs.iter().find(|attr| attr.path().is_ident("error")) {
+            Some(attr) => {
+                let format: LitStr = attr.parse_args()?;
+                generate_display_arm(variant, &format)?
+            }
+            None => {
+                let binding = Ident::new("inner", Span::call_site());
+
+                match (single_field_pattern(enum_name, variant, &binding), VariantShape::of(variant)) {
+                    (Some(pattern), _) => quote!(#pattern => #std::fmt::Display::fmt(#binding, f),),
+                    (None, VariantShape::Unit) => {
+                        let variant_name = &variant.ident;
+                        let name = variant_name.unraw().to_string();
+                        quote!(Self::#variant_name => f.write_str(#name),)
+                    }
+                    (None, _) => {
+                        let message = format!("Variant `{}` has more than one field and needs an #[error(\"...\")] message", variant.ident);
+                        return Err(Error::new_spanned(&variant.ident, message));
+                    }
+                }
+            }
+        };
+
+        arms.push(arm);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics #std::fmt::Display for #enum_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut #std::fmt::Formatter<'_>) -> #std::fmt::Result {
+                match self {
+                    #(#arms)*
+                    #fallback
+                }
+            }
+        }
+    }))
+}
+
+/// Generates the `Debug` impl of `error_enum(debug)`, formatting like `#[derive(Debug)]` but printing the fields
+/// of `#[redact]` variants as `<redacted>`.
+fn generate_debug_impl(options: &Options, enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> TokenStream2 {
+    let std = options.std_crate();
+    let fallback = options.fallback_arm();
+    let redacted = quote!(&#std::format_args!("<redacted>"));
+
+    let arms = variants.iter().map(|model| {
+        let variant = model.variant;
+        let variant_name = &variant.ident;
+        let name = variant_name.unraw().to_string();
+
+        // Fields are bound to `field_<index>`, so that none of them shadows the formatter
+        let bindings: Vec<Ident> = (0..variant.fields.len()).map(|index| format_ident!("field_{}", index)).collect();
+        let values: Vec<TokenStream2> = match model.redact {
+            true => bindings.iter().map(|_| redacted.clone()).collect(),
+            false => bindings.iter().map(|binding| binding.to_token_stream()).collect(),
+        };
+
+        match VariantShape::of(variant) {
+            VariantShape::Unit => quote!(Self::#variant_name => f.write_str(#name),),
+            VariantShape::Tuple(_) if model.redact => quote! {
+                Self::#variant_name(..) => f.debug_tuple(#name)#(.field(#values))*.finish(),
+            },
+            VariantShape::Tuple(_) => quote! {
+                Self::#variant_name(#(#bindings),*) => f.debug_tuple(#name)#(.field(#values))*.finish(),
+            },
+            VariantShape::Named(fields) => {
+                let names: Vec<&Ident> = fields.iter().filter_map(|field| field.ident.as_ref()).collect();
+                let labels = names.iter().map(|name| name.unraw().to_string());
+
+                let pattern = match model.redact {
+                    true => quote!(Self::#variant_name { .. }),
+                    false => quote!(Self::#variant_name { #(#names: #bindings),* }),
+                };
+
+                quote!(#pattern => f.debug_struct(#name)#(.field(#labels, #values))*.finish(),)
+            }
+        }
+    });
+
+    // Like the derive, every type parameter has to be `Debug` for the fields holding it to be formatted
+    let mut generics = generics.clone();
+    let parameters: Vec<Ident> = generics.type_params().map(|param| param.ident.clone()).collect();
+    generics.make_where_clause().predicates.extend(parameters.iter().map(|param| -> WherePredicate { parse_quote!(#param: #std::fmt::Debug) }));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics #std::fmt::Debug for #enum_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut #std::fmt::Formatter<'_>) -> #std::fmt::Result {
+                match self {
+                    #(#arms)*
+                    #fallback
+                }
+            }
+        }
+    }
+}
+
+/// Match arm writing the `#[error("...")]` message of a variant, binding only the fields it refers to.
+fn generate_display_arm(variant: &Variant, format: &LitStr) -> Result<TokenStream2, Error> {
+    let variant_name = &variant.ident;
+    let (rewritten, referenced) = rewrite_format_string(&format.value());
+
+    // Positional placeholders were rewritten to `_0`, `_1`, ..., which only the fields of a tuple variant are bound to
+    let tuple_fields = match VariantShape::of(variant) {
+        VariantShape::Tuple(fields) => fields.len(),
+        _ => 0,
+    };
+    let positional = referenced.iter().filter_map(|name| name.strip_prefix('_')?.parse::<usize>().ok());
+    if let Some(index) = positional.clone().find(|index| *index >= tuple_fields) {
+        let message = match tuple_fields {
+            0 => format!("Variant `{}` has no tuple fields, so #[error(...)] can only use named placeholders such as `{{source}}`", variant_name),
+            _ => format!("Variant `{}` has no tuple field {} for #[error(...)] to refer to", variant_name, index),
+        };
+        return Err(Error::new_spanned(format, message));
+    }
+
+    let rewritten = LitStr::new(&rewritten, format.span());
+
+    let pattern = match VariantShape::of(variant) {
+        VariantShape::Tuple(fields) => {
+            let bindings = (0..fields.len()).map(|index| {
+                let name = format!("_{}", index);
+                match referenced.contains(&name) {
+                    true => Ident::new(&name, Span::call_site()).into_token_stream(),
+                    false => quote!(_),
+                }
+            });
+            quote!(Self::#variant_name(#(#bindings),*))
+        }
+        VariantShape::Named(fields) => {
+            let bindings = fields
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .filter(|name| referenced.contains(&name.to_string()));
+            quote!(Self::#variant_name { #(#bindings,)* .. })
+        }
+        VariantShape::Unit => quote!(Self::#variant_name),
+    };
+
+    Ok(quote!(#pattern => write!(f, #rewritten),))
+}
+
+/// Rewrites positional `{0}` arguments into `{_0}` so that every field can be captured by name,
+/// and implicit `{}` arguments into the next field in order, as `format!` counts them.
+/// Returns the rewritten format string and the names of all referenced arguments.
+fn rewrite_format_string(format: &str) -> (String, Vec<String>) {
+    let mut rewritten = String::with_capacity(format.len());
+    let mut referenced = Vec::new();
+    let mut next_implicit = 0;
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        rewritten.push(c);
+
+        if c != '{' {
+            continue;
+        }
+
+        // Escaped brace
+        if chars.peek() == Some(&'{') {
+            rewritten.push(chars.next().unwrap());
+            continue;
+        }
+
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '}' || c == ':' {
+                break;
+            }
+            name.push(c);
+            chars.next();
+        }
+
+        if name.is_empty() {
+            name = format!("_{}", next_implicit);
+            next_implicit += 1;
+        } else if name.chars().all(|c| c.is_ascii_digit()) {
+            name = format!("_{}", name);
+        }
+
+        rewritten.push_str(&name);
+        referenced.push(name);
+    }
+
+    (rewritten, referenced)
+}
+
+fn generate_try_from_impl(options: &Options, enum_name: &Ident, generics: &Generics, variants: &[VariantModel], aggregate: &Aggregate) -> Result<Option<TokenStream2>, Error> {
+    let anyhow_variant = &aggregate.variant.ident;
+    let anyhow_type = &aggregate.ty;
+    let binding = Ident::new("inner", Span::call_site());
+    let mut attempts = Vec::new();
+
+    for variant in by_priority(variants).into_iter().filter(|model| model.try_from).map(|model| model.variant) {
+        let (variant_inner_type, variant_value) = match (get_single_field_type(variant), single_field_pattern(enum_name, variant, &binding)) {
+            (Some(ty), Some(value)) => (ty, value),
+            _ => {
+                let message = format!("Variant `{}` needs a single field to be used with #[try_from]", variant.ident);
+                return Err(Error::new_spanned(&variant.ident, message));
+            }
+        };
+
+        // Boxed trait objects downcast into a box
+        let unbox = match options.trait_object_aggregate() {
+            true => quote!(let #binding = *#binding;),
+            false => quote!(),
+        };
+
+        attempts.push(quote! {
+            let value = match value.downcast::<#variant_inner_type>() {
+                Ok(#binding) => {
+                    #unbox
+                    return Ok(#variant_value);
+                }
+                Err(value) => value,
+            };
+        });
+    }
+
+    if attempts.is_empty() {
+        return Ok(None);
+    }
+
+    // `Result` is spelled out, as the enum's module may alias it, e.g. through result_alias
+    let std = options.std_crate();
+    let try_from = options.convert_try_from();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics #try_from<#anyhow_type> for #enum_name #ty_generics #where_clause {
+            type Error = Self;
+
+            fn try_from(value: #anyhow_type) -> #std::result::Result<Self, Self> {
+                #(#attempts)*
+                Err(#enum_name::#anyhow_variant(value.into()))
+            }
+        }
+    }))
+}
+
+/// The variants in the order their types are downcast into, lowest `#[priority(...)]` first and in declaration order otherwise.
+fn by_priority<'a, 'v>(variants: &'a [VariantModel<'v>]) -> Vec<&'a VariantModel<'v>> {
+    let mut sorted: Vec<&VariantModel> = variants.iter().collect();
+    sorted.sort_by_key(|model| model.priority);
+    sorted
+}
+
+/// Returns the number of a `#[priority(N)]` attribute, 0 if absent.
+fn get_priority(variant: &Variant) -> Result<i32, Error> {
+    match variant.attrs.iter().find(|attr| attr.path().is_ident("priority")) {
+        Some(attr) => attr.parse_args::<LitInt>()?.base10_parse(),
+        None => Ok(0),
+    }
+}
+
+fn generate_downcast_from_impl(options: &Options, enum_name: &Ident, generics: &Generics, variants: &[VariantModel], aggregate: &Aggregate) -> Result<TokenStream2, Error> {
+    let anyhow_variant = &aggregate.variant.ident;
+    let anyhow_type = &aggregate.ty;
+    let from = options.convert_from();
+    let mut attempts = Vec::new();
+
+    for model in by_priority(variants) {
+        let variant = model.variant;
+
+        if model.try_from {
+            let message = format!("Variant `{}` is marked with #[try_from], which conflicts with error_enum(downcast_from_anyhow)", variant.ident);
+            return Err(Error::new_spanned(&variant.ident, message));
+        }
+
+        // Variants without a From impl of their own, or routing their inner type into the aggregate anyway, are left out
+        let routed = variant.attrs.iter().any(|attr| ["context", "into_anyhow", "via"].iter().any(|name| attr.path().is_ident(name)));
+        if model.holds_aggregate.is_some() || model.skip_from || model.without_anyhow || routed {
+            continue;
+        }
+
+        let field = match get_source_field(variant)? {
+            Some((_, field)) => field,
+            None => continue,
+        };
+
+        // Only owned concrete types can be downcast into
+        let variant_inner_type = get_converted_type(model, &field.ty)?;
+        if matches!(variant_inner_type, Type::Tuple(_) | Type::Reference(_) | Type::TraitObject(_)) {
+            continue;
+        }
+
+        // Boxed trait objects downcast into a box
+        let inner = match options.trait_object_aggregate() {
+            true => quote!(*inner),
+            false => quote!(inner),
+        };
+
+        attempts.push(quote! {
+            let value = match value.downcast::<#variant_inner_type>() {
+                Ok(inner) => return <Self as #from<#variant_inner_type>>::from(#inner),
+                Err(value) => value,
+            };
+        });
+    }
+
+    let trace = options.trace_conversion(anyhow_type);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics #from<#anyhow_type> for #enum_name #ty_generics #where_clause {
+            fn from(value: #anyhow_type) -> Self {
+                #trace
+                #(#attempts)*
+                #enum_name::#anyhow_variant(value.into())
+            }
+        }
+    })
+}
+
+fn generate_into_aggregate_impl(options: &Options, enum_name: &Ident, generics: &Generics, variants: &[VariantModel], aggregate: Option<&Aggregate>) -> TokenStream2 {
+    let anyhow_type = aggregate.map_or(&options.aggregate, |aggregate| &aggregate.ty);
+    let conversion = generate_aggregate_conversion(options, enum_name, variants, aggregate, &quote!(value));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics From<#enum_name #ty_generics> for #anyhow_type #where_clause {
+            fn from(value: #enum_name #ty_generics) -> Self {
+                #conversion
+            }
+        }
+    }
+}
+
+/// Generates the `into_anyhow(self)` method, converting like the impl of `into_aggregate` without being a trait impl.
+fn generate_into_anyhow_method(options: &Options, vis: &Visibility, enum_name: &Ident, generics: &Generics, variants: &[VariantModel], aggregate: Option<&Aggregate>) -> TokenStream2 {
+    let anyhow_type = aggregate.map_or(&options.aggregate, |aggregate| &aggregate.ty);
+    let conversion = generate_aggregate_conversion(options, enum_name, variants, aggregate, &quote!(self));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[allow(clippy::all)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #vis fn into_anyhow(self) -> #anyhow_type {
+                #conversion
+            }
+        }
+    }
+}
+
+/// The match converting the enum `value` into the aggregate, returning the aggregate variant's inner value
+/// and wrapping the other variants.
+fn generate_aggregate_conversion(options: &Options, enum_name: &Ident, variants: &[VariantModel], aggregate: Option<&Aggregate>, value: &TokenStream2) -> TokenStream2 {
+    let anyhow_type = aggregate.map_or(&options.aggregate, |aggregate| &aggregate.ty);
+    let binding = Ident::new("inner", Span::call_site());
+    let fallback = options.fallback_arm();
+    let mut arms = Vec::new();
+
+    // A boxed trait object or struct aggregate has no `new` and `msg`, but converts from errors and strings
+    let (new, msg) = match options.opaque_aggregate() {
+        true => (quote!(<#anyhow_type>::from), quote!(<#anyhow_type>::from)),
+        false => (quote!(<#anyhow_type>::new), quote!(<#anyhow_type>::msg)),
+    };
+
+    for model in variants {
+        let variant = model.variant;
+        let arm = match single_field_pattern(enum_name, variant, &binding) {
+            Some(pattern) if model.holds_aggregate.is_some() => match model.holds_aggregate {
+                Some(AggregateField::Boxed) => quote!(#pattern => *#binding,),
+                Some(AggregateField::Newtype) => quote!(#pattern => #binding.0,),
+                _ => quote!(#pattern => #binding,),
+            },
+            // Payloads of without_anyhow variants aren't necessarily errors, so only their message is kept
+            Some(pattern) if model.without_anyhow => quote! {
+                #pattern => #msg(#binding.to_string()),
+            },
+            Some(pattern) => quote! {
+                #pattern => #new(#binding),
+            },
+            None => {
+                let pattern = wildcard_pattern(enum_name, variant);
+                let name = variant.ident.unraw().to_string();
+                quote!(#pattern => #msg(#name),)
+            }
+        };
+
+        arms.push(arm);
+    }
+
+    quote! {
+        match #value {
+            #(#arms)*
+            #fallback
+        }
+    }
+}
+
+/// Converts a variant name such as `NotFound` or `HTTPError` into `not_found` or `http_error`.
+fn snake_case(ident: &Ident) -> String {
+    let name = ident.unraw().to_string();
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (index, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_is_lowercase = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+
+            if previous != '_' && (previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lowercase)) {
+                snake.push('_');
+            }
+        }
+
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+/// Creates one method name per variant from `prefix` and the snake cased variant name.
+/// Errors if two variants would get the same method name.
+fn method_names(variants: &[&Variant], prefix: &str) -> Result<Vec<Ident>, Error> {
+    let mut names: Vec<Ident> = Vec::new();
+
+    for variant in variants {
+        let name = format_ident!("{}{}", prefix, snake_case(&variant.ident));
+
+        if let Some(index) = names.iter().position(|existing| existing == &name) {
+            let message = format!(
+                "Variants `{}` and `{}` would both generate a method named `{}`",
+                variants[index].ident, variant.ident, name
+            );
+            return Err(Error::new_spanned(&variant.ident, message));
+        }
+
+        names.push(name);
+    }
+
+    Ok(names)
+}
+
+fn generate_predicates(options: &Options, vis: &Visibility, enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> Result<TokenStream2, Error> {
+    let variants: Vec<&Variant> = variants.iter().map(|model| model.variant).collect();
+
+    let names = method_names(&variants, &options.predicate_prefix)?;
+    let patterns = variants.iter().map(|variant| wildcard_pattern(enum_name, variant));
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[allow(clippy::all)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(
+                #vis fn #names(&self) -> bool {
+                    matches!(self, #patterns)
+                }
+            )*
+        }
+    })
+}
+
+fn generate_accessors(options: &Options, vis: &Visibility, enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> Result<TokenStream2, Error> {
+    let std = options.std_crate();
+    let binding = Ident::new("inner", Span::call_site());
+    let variants: Vec<&Variant> = variants.iter().map(|model| model.variant).filter(|variant| get_single_field_type(variant).is_some()).collect();
+
+    let names = method_names(&variants, &options.accessor_prefix)?;
+    let types = variants.iter().filter_map(|variant| get_single_field_type(variant));
+    let patterns = variants.iter().filter_map(|variant| single_field_pattern(enum_name, variant, &binding));
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[allow(clippy::all)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(
+                #vis fn #names(&self) -> #std::option::Option<&#types> {
+                    match self {
+                        #patterns => Some(#binding),
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+            )*
+        }
+    })
+}
+
+/// Generates a `const fn` constructor per unit and single-field variant, named after the variant in snake case.
+fn generate_const_ctors(options: &Options, vis: &Visibility, enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> Result<TokenStream2, Error> {
+    let binding = Ident::new("value", Span::call_site());
+    let variants: Vec<&Variant> = variants.iter()
+        .map(|model| model.variant)
+        .filter(|variant| matches!(variant.fields, Fields::Unit) || get_single_field_type(variant).is_some())
+        .collect();
+
+    // The other generated methods are prefixed, except for `kind` and `into_anyhow`
+    let names = method_names(&variants, "")?;
+    let reserved = [("kind", options.kind), ("into_anyhow", options.into_anyhow_method)];
+    if let Some((variant, name)) = variants.iter().zip(&names).find(|(_, name)| reserved.iter().any(|(reserved, enabled)| *enabled && *name == reserved)) {
+        let message = format!("const_ctors would generate a constructor `{}` for `{}`, which is already a generated method", name, variant.ident);
+        return Err(Error::new_spanned(&variant.ident, message));
+    }
+
+    // Keywords such as `type` can only be used as method names in their raw form, which path keywords have none of
+    if let Some((variant, name)) = variants.iter().zip(&names).find(|(_, name)| ["self", "super", "crate"].iter().any(|keyword| *name == keyword)) {
+        let message = format!("const_ctors can't name the constructor of `{}` `{}`, which is a keyword", variant.ident, name);
+        return Err(Error::new_spanned(&variant.ident, message));
+    }
+
+    let ctors = variants.iter().zip(names).map(|(variant, name)| {
+        let name = match syn::parse_str::<Ident>(&name.to_string()) {
+            Ok(_) => name,
+            Err(_) => Ident::new_raw(&name.to_string(), name.span()),
+        };
+
+        match get_single_field_type(variant) {
+            Some(ty) => {
+                let value = single_field_pattern(enum_name, variant, &binding);
+                quote! {
+                    #vis const fn #name(#binding: #ty) -> Self {
+                        #value
+                    }
+                }
+            }
+            None => {
+                let variant_name = &variant.ident;
+                quote! {
+                    #vis const fn #name() -> Self {
+                        #enum_name::#variant_name
+                    }
+                }
+            }
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[allow(clippy::all)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(#ctors)*
+        }
+    })
+}
+
+/// Generates an `into_<variant>` method per single-field variant, consuming the enum to return its field.
+fn generate_into_inner(options: &Options, vis: &Visibility, enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> Result<TokenStream2, Error> {
+    let binding = Ident::new("inner", Span::call_site());
+    let variants: Vec<&Variant> = variants.iter().map(|model| model.variant).filter(|variant| get_single_field_type(variant).is_some()).collect();
+
+    let names = method_names(&variants, "into_")?;
+    let types = variants.iter().filter_map(|variant| get_single_field_type(variant));
+    let patterns = variants.iter().filter_map(|variant| single_field_pattern(enum_name, variant, &binding));
+
+    let std = options.std_crate();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[allow(clippy::all)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(
+                #vis fn #names(self) -> #std::result::Result<#types, Self> {
+                    match self {
+                        #patterns => Ok(#binding),
+                        #[allow(unreachable_patterns)]
+                        other => Err(other),
+                    }
+                }
+            )*
+        }
+    })
+}
+
+/// Generates the fieldless `<Enum>Kind` enum mirroring the variants, with the same visibility as the enum,
+/// and separately the impl of the `kind()` method.
+fn generate_kind(options: &Options, vis: &Visibility, input: &DeriveInput, variants: &[VariantModel]) -> (TokenStream2, TokenStream2) {
+    let fallback = options.fallback_arm();
+    let variants: Vec<&Variant> = variants.iter().map(|model| model.variant).collect();
+    let enum_name = &input.ident;
+    let visibility = &input.vis;
+    let kind_name = format_ident!("{}Kind", enum_name);
+    let kind_doc = format!("The variants of [`{}`], without their fields.", enum_name);
+
+    let variant_names: Vec<&Ident> = variants.iter().map(|variant| &variant.ident).collect();
+    let patterns = variants.iter().map(|variant| wildcard_pattern(enum_name, variant));
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let definition = quote! {
+        #[doc = #kind_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #visibility enum #kind_name {
+            #(#variant_names,)*
+        }
+    };
+
+    let kind_impl = quote! {
+        #[allow(clippy::all)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #vis fn kind(&self) -> #kind_name {
+                match self {
+                    #(#patterns => #kind_name::#variant_names,)*
+                    #fallback
+                }
+            }
+        }
+    };
+
+    (definition, kind_impl)
+}
+
+/// Generates the axum `IntoResponse` impl, answering with the `#[status(...)]` of the variant and the `Display` of the error.
+fn generate_into_response_impl(enum_name: &Ident, generics: &Generics, variants: &[VariantModel]) -> Result<TokenStream2, Error> {
+    let mut arms = Vec::new();
+
+    for model in variants {
+        let variant = model.variant;
+        let attr = match variant.attrs.iter().find(|attr| attr.path().is_ident("status")) {
+            Some(attr) => attr,
+            None => continue,
+        };
+
+        let status = match attr.parse_args::<Expr>()? {
+            Expr::Lit(ExprLit { lit: Lit::Int(code), .. }) => {
+                if !(100..=999).contains(&code.base10_parse::<u16>()?) {
+                    return Err(Error::new_spanned(code, "status codes have to be between 100 and 999"));
+                }
+
+                quote!(axum::http::StatusCode::from_u16(#code).unwrap())
+            }
+            // `StatusCode::NOT_FOUND` is resolved through axum, other paths are taken as written
+            Expr::Path(path) if path.path.segments.len() == 2 && path.path.segments[0].ident == "StatusCode" => quote!(axum::http::#path),
+            status => quote!(#status),
+        };
+
+        let pattern = wildcard_pattern(enum_name, variant);
+        arms.push(quote!(#pattern => #status,));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics axum::response::IntoResponse for #enum_name #ty_generics #where_clause {
+            fn into_response(self) -> axum::response::Response {
+                let status = match &self {
+                    #(#arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                };
+
+                axum::response::IntoResponse::into_response((status, self.to_string()))
+            }
+        }
+    })
+}
+
+/// Generates the `FromStr` impl of `from_str(...)`, delegating to the user's function.
+fn generate_from_str_impl(options: &Options, enum_name: &Ident, generics: &Generics, function: &Path, error: &Type) -> TokenStream2 {
+    let std = options.std_crate();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics #std::str::FromStr for #enum_name #ty_generics #where_clause {
+            type Err = #error;
+
+            fn from_str(s: &str) -> #std::result::Result<Self, Self::Err> {
+                #function(s)
+            }
+        }
+    }
+}
+
+/// Generates the serde `Serialize` impl, writing the variant name as `kind` and the error as `message`.
+/// The message is the generated `Display`, or `Debug` if there is none, the fields are never serialized.
+fn generate_serialize_impl(options: &Options, enum_name: &Ident, generics: &Generics, variants: &[VariantModel], has_display: bool) -> TokenStream2 {
+    let std = options.std_crate();
+    let enum_name_string = enum_name.unraw().to_string();
+    let patterns = variants.iter().map(|model| wildcard_pattern(enum_name, model.variant));
+    let kinds = variants.iter().map(|model| model.variant.ident.unraw().to_string());
+    let fallback = options.fallback_arm();
+
+    let message = match has_display {
+        true => quote!(format_args!("{}", self)),
+        false => quote!(format_args!("{:?}", self)),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        #[allow(clippy::all)]
+        impl #impl_generics serde::Serialize for #enum_name #ty_generics #where_clause {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> #std::result::Result<S::Ok, S::Error> {
+                use serde::ser::SerializeStruct;
+
+                let kind = match self {
+                    #(#patterns => #kinds,)*
+                    #fallback
+                };
+
+                let mut state = serializer.serialize_struct(#enum_name_string, 2)?;
+                state.serialize_field("kind", kind)?;
+                state.serialize_field("message", &#message)?;
+                state.end()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_finds_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Db(DbError),
+                Other(anyhow::Error),
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+        let aggregate = model.aggregate.as_ref().unwrap();
+
+        assert_eq!(aggregate.variant.ident, "Other");
+        assert_eq!(aggregate.field, AggregateField::Plain);
+        assert!(model.is_aggregate(model.variants[1].variant));
+        assert!(!model.is_aggregate(model.variants[0].variant));
+    }
+
+    #[test]
+    fn model_finds_boxed_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(Box<anyhow::Error>),
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+        let aggregate = model.aggregate.as_ref().unwrap();
+
+        assert_eq!(aggregate.field, AggregateField::Boxed);
+        assert!(types_match(&aggregate.ty, &parse_quote!(anyhow::Error)));
+    }
+
+    #[test]
+    fn model_finds_newtype_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[deref_anyhow]
+                Wrapped(AppErr),
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+        let aggregate = model.aggregate.as_ref().unwrap();
+
+        assert_eq!(aggregate.variant.ident, "Wrapped");
+        assert_eq!(aggregate.field, AggregateField::Newtype);
+    }
+
+    #[test]
+    fn model_without_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            #[error_enum(no_aggregate)]
+            enum MyError {
+                Db(DbError),
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+
+        assert!(model.aggregate.is_none());
+        assert!(model.options.no_aggregate);
+    }
+
+    #[test]
+    fn model_reads_variant_flags() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[skip_from]
+                Skipped(SkippedError),
+                #[without_anyhow]
+                #[boxed]
+                Large(Box<LargeError>),
+                #[try_from]
+                Downcast(DowncastError),
+                #[wrap]
+                #[from_types(std::io::Error, std::fmt::Error)]
+                Wrapped(WrappedError),
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+        let flags: Vec<_> = model.variants
+            .iter()
+            .map(|variant| (variant.skip_from, variant.try_from, variant.without_anyhow, variant.wrap, variant.boxed))
+            .collect();
+
+        assert_eq!(flags, [
+            (false, false, false, false, false),
+            (true, false, false, false, false),
+            (false, false, true, false, true),
+            (false, true, false, false, false),
+            (false, false, false, true, false),
+        ]);
+        assert_eq!(model.variants[4].from_types.len(), 2);
+        assert!(model.variants[..4].iter().all(|variant| variant.from_types.is_empty()));
+    }
+
+    #[test]
+    fn model_rejects_missing_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Db(DbError),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.to_string(), "Could not find a variant with anyhow::Error type in this enum");
+    }
+
+    #[test]
+    fn model_rejects_multi_field_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Any(anyhow::Error, String),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Variant `Any` holds anyhow::Error next to other fields, but the aggregate variant has to hold it as its single field, such as `Any(anyhow::Error)`"
+        );
+    }
+
+    #[test]
+    fn model_rejects_named_field_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Any { source: anyhow::Error },
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Variant `Any` holds anyhow::Error in the named field `source`, but the aggregate variant has to hold it in a tuple field, such as `Any(anyhow::Error)`"
+        );
+    }
+
+    #[test]
+    fn build_with_rejects_holding_the_enum() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[boxed]
+                #[build_with(|error| MyError::Nested(Box::new(error)))]
+                Nested(Box<MyError>),
+            }
+        };
+
+        let error = expand(&input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Variant `Nested` holds `MyError` itself, whose From impl would conflict with the blanket `impl From<T> for T`, mark it with #[skip_from]"
+        );
+    }
+
+    #[test]
+    fn display_maps_implicit_placeholders_to_fields() {
+        let (rewritten, referenced) = rewrite_format_string("{} failed after {:?} ({0}, {{}})");
+
+        assert_eq!(rewritten, "{_0} failed after {_1:?} ({_0}, {{}})");
+        assert_eq!(referenced, ["_0", "_1", "_0"]);
+    }
+
+    #[test]
+    fn display_rejects_missing_positional_fields() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[error("timed out after {} of {}")]
+                Timeout(u64),
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+        let error = generate(&input, &model).err().unwrap();
+        assert_eq!(error.to_string(), "Variant `Timeout` has no tuple field 1 for #[error(...)] to refer to");
+
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[error("config {0} is invalid")]
+                Config { path: String },
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+        let error = generate(&input, &model).err().unwrap();
+        assert_eq!(error.to_string(), "Variant `Config` has no tuple fields, so #[error(...)] can only use named placeholders such as `{source}`");
+    }
+
+    #[test]
+    fn strict_reports_every_variant_without_from() {
+        let input: DeriveInput = parse_quote! {
+            #[error_enum(strict)]
+            enum MyError {
+                Other(anyhow::Error),
+                NotFound,
+                Pair(DbError, String),
+                #[skip_from]
+                Ignored,
+                Marked(#[from] DbError, String),
+            }
+        };
+
+        let messages: Vec<String> = expand(&input).err().unwrap().into_iter().map(|error| error.to_string()).collect();
+        assert_eq!(messages, [
+            "Variant `NotFound` gets no From impl as it has no field to convert from, but error_enum(strict) requires one unless it is marked with #[skip_from]",
+            "Variant `Pair` gets no From impl as it has several fields and none is marked with #[from], but error_enum(strict) requires one unless it is marked with #[skip_from]",
+        ]);
+    }
+
+    #[test]
+    fn const_ctors_reject_generated_method_names() {
+        let input: DeriveInput = parse_quote! {
+            #[error_enum(const_ctors, kind)]
+            enum MyError {
+                Other(anyhow::Error),
+                Kind,
+            }
+        };
+
+        let error = expand(&input).err().unwrap();
+        assert_eq!(error.to_string(), "const_ctors would generate a constructor `kind` for `Kind`, which is already a generated method");
+    }
+
+    #[test]
+    fn whole_enum_options_reject_cfg_variants() {
+        let input: DeriveInput = parse_quote! {
+            #[error_enum(kind)]
+            enum MyError {
+                Other(anyhow::Error),
+                #[cfg(any())]
+                Cache(CacheError),
+                #[cfg(test)]
+                #[try_from]
+                Db(DbError),
+            }
+        };
+
+        let messages: Vec<String> = expand(&input).err().unwrap().into_iter().map(|error| error.to_string()).collect();
+        assert_eq!(messages, [
+            "Variant `Cache` is gated with #[cfg(...)], which error_enum(kind) doesn't support yet, as it would name the variant where it is disabled",
+            "Variant `Db` is gated with #[cfg(...)], which error_enum(kind) doesn't support yet, as it would name the variant where it is disabled",
+        ]);
+
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[cfg(test)]
+                #[try_from]
+                Db(DbError),
+            }
+        };
+
+        let error = expand(&input).err().unwrap();
+        assert_eq!(error.to_string(), "Variant `Db` is gated with #[cfg(...)], which #[try_from] doesn't support yet, as it would name the variant where it is disabled");
+    }
+
+    #[test]
+    fn result_alias_rejects_generic_enums() {
+        let input: DeriveInput = parse_quote! {
+            #[error_enum(result_alias = "Result")]
+            enum MyError<T> {
+                Other(anyhow::Error),
+                Rejected(T),
+            }
+        };
+
+        let error = expand(&input).err().unwrap();
+        assert_eq!(error.to_string(), "result_alias only supports enums without generic parameters, the alias has to be written by hand otherwise");
+    }
+
+    #[test]
+    fn model_rejects_empty_enum() {
+        let input: DeriveInput = parse_quote! {
+            enum Empty {}
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.to_string(), "ErrorEnum requires at least one variant; `Empty` has none");
+    }
+
+    #[test]
+    fn model_rejects_without_anyhow_on_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                #[without_anyhow]
+                Other(anyhow::Error),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.to_string(), "#[without_anyhow] is meaningless on `Other`, the aggregate variant holding the anyhow::Error itself");
+    }
+
+    #[test]
+    fn model_rejects_conflicting_attributes() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[context("loading config")]
+                #[without_anyhow]
+                Config(ConfigError),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.to_string(), "Variant `Config` is marked with #[without_anyhow] and #[context], but context is only attached in the aggregate");
+    }
+
+    #[test]
+    fn model_rejects_from_on_without_anyhow_variant() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[without_anyhow]
+                #[from]
+                Decode(DecodeError),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.to_string(), "Variant `Decode` is marked with #[from] and #[without_anyhow], but #[from] selects the field to convert from and belongs on that field");
+    }
+
+    #[test]
+    fn model_picks_default_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Fatal(anyhow::Error),
+                #[default_anyhow]
+                Internal(anyhow::Error),
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+
+        assert_eq!(model.aggregate.as_ref().unwrap().variant.ident, "Internal");
+        assert!(model.variants.iter().all(|variant| variant.holds_aggregate == Some(AggregateField::Plain)));
+    }
+
+    #[test]
+    fn model_rejects_several_aggregates_without_default() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Fatal(anyhow::Error),
+                Internal(anyhow::Error),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Variants `Fatal`, `Internal` all hold anyhow::Error, mark the one From<anyhow::Error> converts into with #[default_anyhow]"
+        );
+    }
+
+    #[test]
+    fn model_rejects_several_defaults() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                #[default_anyhow]
+                Fatal(anyhow::Error),
+                #[default_anyhow]
+                Internal(anyhow::Error),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.to_string(), "Variant `Internal` is marked with #[default_anyhow] as well, but only one variant can be the default");
+    }
+
+    #[test]
+    fn model_rejects_default_without_aggregate() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Fatal(anyhow::Error),
+                #[default_anyhow]
+                Db(DbError),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.to_string(), "Variant `Db` is marked with #[default_anyhow] but doesn't hold anyhow::Error");
+    }
+
+    #[test]
+    fn model_rejects_flag_arguments() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[without_anyhow(true)]
+                Db(DbError),
+            }
+        };
+
+        assert!(parse_enum(&input).is_err());
+    }
+
+    #[test]
+    fn model_reports_every_variant_error() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[without_anyhow(true)]
+                Db(DbError),
+                #[wrap = "yes"]
+                Io(IoError),
+            }
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.into_iter().count(), 2);
+    }
+
+    #[test]
+    fn generate_reports_independent_errors() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                Db(DbError),
+                Cache(DbError),
+                #[wrap]
+                #[map_anyhow(redact)]
+                Io(IoError),
+            }
+        };
+
+        let model = parse_enum(&input).unwrap();
+        let messages: Vec<String> = generate(&input, &model).err().unwrap().into_iter().map(|error| error.to_string()).collect();
+
+        assert_eq!(messages, [
+            "Variants `Db` and `Cache` both wrap `DbError`, which would generate conflicting From impls",
+            "Variant `Io` is marked with #[map_anyhow], but is wrapped without extracting anything to map",
+        ]);
+    }
+
+    #[test]
+    fn listed_type_collision_names_the_listing_variant() {
+        let listed_first: DeriveInput = parse_quote! {
+            enum MyError {
+                #[from_types(std::fmt::Error)]
+                Other(anyhow::Error),
+                Format(std::fmt::Error),
+            }
+        };
+        let listed_last: DeriveInput = parse_quote! {
+            enum MyError {
+                Format(std::fmt::Error),
+                #[from_types(std::fmt::Error)]
+                Other(anyhow::Error),
+            }
+        };
+
+        for input in [listed_first, listed_last] {
+            let error = expand(&input).err().unwrap();
+            assert_eq!(error.to_string(), "Variant `Other` lists `std::fmt::Error` in #[from_types], but `Format` already converts from it");
+        }
+    }
+
+    #[test]
+    fn from_impl_is_located_at_variant() {
+        let input: DeriveInput = syn::parse_str("enum MyError {\n    Other(anyhow::Error),\n    Db(DbError),\n}").unwrap();
+        let tokens = expand(&input).unwrap();
+
+        let impl_span = tokens.into_iter()
+            .find(|token| matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == "impl"))
+            .map(|token| token.span())
+            .unwrap();
+
+        // `Db` starts on the third line, after four spaces
+        assert_eq!((impl_span.start().line, impl_span.start().column), (3, 4));
+    }
+
+    #[test]
+    fn missing_aggregate_is_located_at_enum_name() {
+        let input: DeriveInput = syn::parse_str("pub enum MyError {\n    Db(DbError),\n}").unwrap();
+        let error = parse_enum(&input).err().unwrap();
+
+        // `MyError` follows `pub enum ` on the first line
+        assert_eq!((error.span().start().line, error.span().start().column), (1, 9));
+    }
+
+    #[test]
+    fn duplicate_type_is_located_at_second_variant() {
+        let input: DeriveInput = syn::parse_str("enum MyError {\n    Other(anyhow::Error),\n    Db(DbError),\n    Cache(DbError),\n}").unwrap();
+        let error = expand(&input).err().unwrap();
+        assert_eq!(error.to_string(), "Variants `Db` and `Cache` both wrap `DbError`, which would generate conflicting From impls");
+
+        // `Cache` starts on the fourth line, after four spaces
+        assert_eq!((error.span().start().line, error.span().start().column), (4, 4));
+    }
+
+    #[test]
+    fn matches_trait_object_bounds() {
+        let send_sync: Type = parse_quote!(Box<dyn std::error::Error + Send + Sync>);
+
+        assert!(types_match(&send_sync, &parse_quote!(Box<dyn std::error::Error + Sync + Send>)));
+        assert!(!types_match(&send_sync, &parse_quote!(Box<dyn std::error::Error>)));
+        assert!(!types_match(&send_sync, &parse_quote!(Box<dyn std::error::Error + Send>)));
+    }
+
+    #[test]
+    fn matches_bounds_with_leading_colons() {
+        assert!(types_match(&parse_quote!(Box<dyn ::std::error::Error>), &parse_quote!(Box<dyn std::error::Error>)));
+        assert!(types_match(&parse_quote!(Box<dyn ::core::fmt::Debug + Send>), &parse_quote!(Box<dyn Send + core::fmt::Debug>)));
+        assert!(types_match(&parse_quote!(impl ::std::error::Error), &parse_quote!(impl std::error::Error)));
+        assert!(!types_match(&parse_quote!(Box<dyn ::std::error::Error>), &parse_quote!(Box<dyn std::fmt::Debug>)));
+    }
+
+    #[test]
+    fn detects_enum_type() {
+        let enum_name: Ident = parse_quote!(MyError);
+
+        assert!(is_enum_type(&parse_quote!(MyError), &enum_name));
+        assert!(is_enum_type(&parse_quote!(MyError<T>), &enum_name));
+        assert!(is_enum_type(&parse_quote!(Self), &enum_name));
+        assert!(!is_enum_type(&parse_quote!(Box<MyError>), &enum_name));
+        assert!(!is_enum_type(&parse_quote!(other::MyError), &enum_name));
+    }
+
+    #[test]
+    fn expand_generates_conversions() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                Other(anyhow::Error),
+                #[without_anyhow]
+                Db(DbError),
+                Io(IoError),
+            }
+        };
+
+        let expected = quote! {
+            #[automatically_derived]
+            #[allow(clippy::all)]
+            impl From<DbError> for MyError {
+                fn from(value: DbError) -> Self {
+                    Self::Db(value)
+                }
+            }
+
+            #[automatically_derived]
+            #[allow(clippy::all)]
+            impl From<IoError> for MyError {
+                fn from(value: IoError) -> Self {
+                    match value {
+                        IoError::Other(e) => MyError::Other(e),
+                        #[allow(unreachable_patterns)]
+                        _ => MyError::Io(value),
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            #[allow(clippy::all)]
+            impl From<anyhow::Error> for MyError {
+                fn from(value: anyhow::Error) -> Self {
+                    MyError::Other(value.into())
+                }
+            }
+        };
+
+        assert_eq!(expand(&input).unwrap().to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn expand_generates_predicates() {
+        let input: DeriveInput = parse_quote! {
+            #[error_enum(no_aggregate, predicates)]
+            pub enum MyError {
+                NotFound,
+            }
+        };
+
+        let expected = quote! {
+            #[allow(clippy::all)]
+            impl MyError {
+                pub fn is_not_found(&self) -> bool {
+                    matches!(self, MyError::NotFound)
+                }
+            }
+        };
+
+        assert_eq!(expand(&input).unwrap().to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn expand_rejects_unknown_options() {
+        let input: DeriveInput = parse_quote! {
+            #[error_enum(unknown)]
+            enum MyError {
+                Other(anyhow::Error),
+            }
+        };
+
+        let message = expand(&input).err().unwrap().to_string();
+        assert!(message.starts_with("unknown error_enum option `unknown`, expected one of: aggregate, aggregate_kind, aggregate_any_of,"));
+        assert!(OPTION_KEYS.iter().all(|key| message.contains(key)));
+    }
+
+    #[test]
+    fn options_parse_every_key() {
+        let attrs: Vec<Attribute> = vec![parse_quote! {
+            #[error_enum(
+                aggregate = "eyre::Report", vis = "pub(crate)", predicate_prefix = "check_", accessor_prefix = "get_",
+                std_error, no_aggregate, into_aggregate, into_anyhow_method, no_anyhow_from, downcast_from_anyhow, strict,
+                no_std, predicates, accessors, into_inner, const_ctors, kind
+            )]
+        }];
+
+        let options = parse_options(&attrs).unwrap();
+
+        assert!(types_match(&options.aggregate, &parse_quote!(eyre::Report)));
+        assert!(matches!(options.vis, Some(Visibility::Restricted(_))));
+        assert_eq!((options.predicate_prefix.as_str(), options.accessor_prefix.as_str()), ("check_", "get_"));
+        assert!(options.std_error && options.no_aggregate && options.into_aggregate && options.into_anyhow_method);
+        assert!(options.no_anyhow_from && options.downcast_from_anyhow && options.strict && options.no_std);
+        assert!(options.predicates && options.accessors && options.into_inner && options.const_ctors && options.kind);
+        assert!(!options.trace && !options.into_response && !options.serialize && !options.non_exhaustive);
+    }
+
+    #[test]
+    fn options_parse_from_str() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[error_enum(from_str(codes::parse, ParseCodeError))])];
+        let (function, error) = parse_options(&attrs).unwrap().from_str.unwrap();
+        assert_eq!(function, parse_quote!(codes::parse));
+        assert!(types_match(&error, &parse_quote!(ParseCodeError)));
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[error_enum(from_str(codes::parse))])];
+        let message = parse_options(&attrs).err().unwrap().to_string();
+        assert_eq!(message, "from_str expects the parsing function and its error type, such as from_str(parse_code, ParseCodeError)");
+    }
+
+    #[test]
+    fn options_parse_aggregate_any_of() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[error_enum(aggregate_any_of("anyhow::Error", "eyre::Report"))])];
+
+        let options = parse_options(&attrs).unwrap();
+        assert_eq!(options.aggregate_any_of.len(), 2);
+    }
+
+    #[test]
+    fn options_check_features() {
+        for (key, feature) in [("trace", cfg!(feature = "tracing")), ("into_response", cfg!(feature = "axum")), ("serialize", cfg!(feature = "serde"))] {
+            let key = Ident::new(key, Span::call_site());
+            let attrs: Vec<Attribute> = vec![parse_quote!(#[error_enum(#key)])];
+            assert_eq!(parse_options(&attrs).is_ok(), feature, "{}", key);
+        }
+    }
+
+    #[test]
+    fn model_rejects_structs() {
+        let input: DeriveInput = parse_quote! {
+            struct MyError(anyhow::Error);
+        };
+
+        let error = parse_enum(&input).err().unwrap();
+        assert_eq!(error.to_string(), "ErrorEnum is only implemented for enums");
+    }
+}
@@ -0,0 +1,51 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+enum ApplicationError {
+    Invalid,
+}
+
+#[derive(Debug, PartialEq)]
+struct DatabaseError;
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(accessors)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    ApplicationError(ApplicationError),
+
+    #[without_anyhow]
+    Database { source: DatabaseError },
+
+    NotFound,
+}
+
+#[test]
+fn tuple_accessor() {
+    let error = AppError::from(ApplicationError::Invalid);
+    assert_eq!(error.as_application_error(), Some(&ApplicationError::Invalid));
+    assert!(error.as_database().is_none());
+    assert!(error.as_anyhow().is_none());
+}
+
+#[test]
+fn named_accessor() {
+    let error = AppError::from(DatabaseError);
+    assert_eq!(error.as_database(), Some(&DatabaseError));
+    assert!(error.as_application_error().is_none());
+}
+
+#[test]
+fn aggregate_accessor() {
+    let error = AppError::from(anyhow::Error);
+    assert!(error.as_anyhow().is_some());
+    assert!(AppError::NotFound.as_anyhow().is_none());
+    assert!(AppError::NotFound.as_application_error().is_none());
+}
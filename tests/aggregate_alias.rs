@@ -0,0 +1,50 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+type Dynamic = anyhow::Error;
+
+#[derive(Debug)]
+enum DatabaseError {
+    Any(Dynamic),
+    Timeout,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(predicates)]
+enum AppError {
+    #[anyhow]
+    Any(Dynamic),
+
+    Database(DatabaseError),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum BoxedError {
+    #[anyhow]
+    Any(Box<Dynamic>),
+}
+
+#[test]
+fn marked_variant_is_the_aggregate() {
+    let error = AppError::from(anyhow::Error);
+    assert!(error.is_any());
+}
+
+#[test]
+fn extracts_into_marked_variant() {
+    let error = AppError::from(DatabaseError::Any(anyhow::Error));
+    assert!(error.is_any());
+
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn marked_boxed_variant() {
+    let BoxedError::Any(inner) = BoxedError::from(anyhow::Error);
+    assert!(matches!(*inner, anyhow::Error));
+}
@@ -0,0 +1,44 @@
+use error_conversion_macro::ErrorEnum;
+
+mod eyre {
+    #[derive(Debug)]
+    pub struct Report;
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(eyre::Report),
+    Timeout,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate_any_of("anyhow::Error", "eyre::Report"))]
+enum AppError {
+    Anyhow(eyre::Report),
+
+    Database(DatabaseError),
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate_any_of("anyhow::Error", "eyre::Report"))]
+enum BoxedError {
+    Anyhow(Box<eyre::Report>),
+}
+
+#[test]
+fn held_candidate_is_the_aggregate() {
+    let error = AppError::from(eyre::Report);
+    assert!(matches!(error, AppError::Anyhow(eyre::Report)));
+
+    let error = AppError::from(DatabaseError::Anyhow(eyre::Report));
+    assert!(matches!(error, AppError::Anyhow(eyre::Report)));
+
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn boxed_candidate() {
+    let BoxedError::Anyhow(inner) = BoxedError::from(eyre::Report);
+    assert!(matches!(*inner, eyre::Report));
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(pub String);
+}
+
+// A newtype around the aggregate, with none of its methods
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct AppErr(anyhow::Error);
+
+impl From<DatabaseError> for AppErr {
+    fn from(error: DatabaseError) -> Self {
+        AppErr(anyhow::Error(error.to_string()))
+    }
+}
+
+impl From<std::fmt::Error> for AppErr {
+    fn from(error: std::fmt::Error) -> Self {
+        AppErr(anyhow::Error(error.to_string()))
+    }
+}
+
+impl From<String> for AppErr {
+    fn from(message: String) -> Self {
+        AppErr(anyhow::Error(message))
+    }
+}
+
+impl From<&str> for AppErr {
+    fn from(message: &str) -> Self {
+        AppErr(anyhow::Error(message.to_string()))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum DatabaseError {
+    Timeout,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("database timed out")
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "AppErr", aggregate_kind = "struct", into_aggregate)]
+enum AppError {
+    #[from_types(std::fmt::Error)]
+    Other(AppErr),
+
+    Database(DatabaseError),
+
+    #[without_anyhow]
+    Status(u16),
+
+    NotFound,
+}
+
+#[test]
+fn inner_enums_are_wrapped() {
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn aggregate_is_converted_with_from() {
+    let error = AppError::from(AppErr::from("boom"));
+    assert!(matches!(error, AppError::Other(AppErr(anyhow::Error(message))) if message == "boom"));
+
+    let error = AppError::from(fmt::Error);
+    assert!(matches!(error, AppError::Other(AppErr(anyhow::Error(message))) if message == "an error occurred when formatting an argument"));
+}
+
+#[test]
+fn converts_back_with_from() {
+    let AppErr(anyhow::Error(message)) = AppError::from(DatabaseError::Timeout).into();
+    assert_eq!(message, "database timed out");
+
+    let AppErr(anyhow::Error(message)) = AppError::from(404).into();
+    assert_eq!(message, "404");
+
+    let AppErr(anyhow::Error(message)) = AppError::NotFound.into();
+    assert_eq!(message, "NotFound");
+}
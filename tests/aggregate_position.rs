@@ -0,0 +1,73 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(pub &'static str);
+}
+
+// Inner enums holding the aggregate first and last, independently of where ours hold it
+#[derive(Debug)]
+enum LeadingError {
+    Anyhow(anyhow::Error),
+    Closed,
+}
+
+#[derive(Debug)]
+enum TrailingError {
+    Closed,
+    Anyhow(anyhow::Error),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum FirstError {
+    Anyhow(anyhow::Error),
+    Leading(LeadingError),
+    Trailing(TrailingError),
+    #[without_anyhow]
+    Code(u16),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum MiddleError {
+    Leading(LeadingError),
+    Anyhow(anyhow::Error),
+    #[without_anyhow]
+    Code(u16),
+    Trailing(TrailingError),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum LastError {
+    #[without_anyhow]
+    Code(u16),
+    Leading(LeadingError),
+    Trailing(TrailingError),
+    Anyhow(anyhow::Error),
+}
+
+macro_rules! position_tests {
+    ($($test: ident: $enum: ident,)*) => {
+        $(
+            #[test]
+            fn $test() {
+                assert!(matches!($enum::from(anyhow::Error("direct")), $enum::Anyhow(anyhow::Error("direct"))));
+
+                let error = $enum::from(LeadingError::Anyhow(anyhow::Error("leading")));
+                assert!(matches!(error, $enum::Anyhow(anyhow::Error("leading"))));
+
+                let error = $enum::from(TrailingError::Anyhow(anyhow::Error("trailing")));
+                assert!(matches!(error, $enum::Anyhow(anyhow::Error("trailing"))));
+
+                assert!(matches!($enum::from(LeadingError::Closed), $enum::Leading(LeadingError::Closed)));
+                assert!(matches!($enum::from(TrailingError::Closed), $enum::Trailing(TrailingError::Closed)));
+                assert!(matches!($enum::from(404), $enum::Code(404)));
+            }
+        )*
+    };
+}
+
+position_tests! {
+    aggregate_declared_first: FirstError,
+    aggregate_declared_in_the_middle: MiddleError,
+    aggregate_declared_last: LastError,
+}
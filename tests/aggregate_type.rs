@@ -0,0 +1,83 @@
+// Makes `::anyhow::Error` resolve to the `Error` struct below.
+extern crate self as anyhow;
+
+use error_conversion_macro::ErrorEnum;
+
+#[derive(Debug)]
+pub struct Error;
+
+#[derive(Debug)]
+enum ApplicationError {
+    AnyhowError(anyhow::Error),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum PlainPathError {
+    AnyhowError(anyhow::Error),
+    ApplicationError(ApplicationError),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum LeadingColonError {
+    AnyhowError(::anyhow::Error),
+    ApplicationError(ApplicationError),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum SpacedPathError {
+    AnyhowError(anyhow :: Error),
+    ApplicationError(ApplicationError),
+}
+
+#[test]
+fn plain_path() {
+    let error = PlainPathError::from(ApplicationError::AnyhowError(Error));
+    assert!(matches!(error, PlainPathError::AnyhowError(_)));
+
+    let error = PlainPathError::from(Error);
+    assert!(matches!(error, PlainPathError::AnyhowError(_)));
+}
+
+#[test]
+fn leading_colon() {
+    let error = LeadingColonError::from(ApplicationError::AnyhowError(Error));
+    assert!(matches!(error, LeadingColonError::AnyhowError(_)));
+
+    let error = LeadingColonError::from(Error);
+    assert!(matches!(error, LeadingColonError::AnyhowError(_)));
+}
+
+#[test]
+fn spaced_path() {
+    let error = SpacedPathError::from(ApplicationError::AnyhowError(Error));
+    assert!(matches!(error, SpacedPathError::AnyhowError(_)));
+
+    let error = SpacedPathError::from(Error);
+    assert!(matches!(error, SpacedPathError::AnyhowError(_)));
+}
+
+mod eyre {
+    #[derive(Debug)]
+    pub struct Report;
+}
+
+#[derive(Debug)]
+enum ReportingError {
+    Report(eyre::Report),
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "eyre::Report")]
+enum ConfiguredAggregateError {
+    Report(eyre::Report),
+    Reporting(ReportingError),
+}
+
+#[test]
+fn configured_aggregate() {
+    let error = ConfiguredAggregateError::from(ReportingError::Report(eyre::Report));
+    assert!(matches!(error, ConfiguredAggregateError::Report(_)));
+
+    let error = ConfiguredAggregateError::from(eyre::Report);
+    assert!(matches!(error, ConfiguredAggregateError::Report(_)));
+}
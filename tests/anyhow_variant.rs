@@ -0,0 +1,46 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Unexpected(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug)]
+enum CacheError {
+    Any(anyhow::Error),
+    Miss,
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Any(anyhow::Error),
+
+    #[anyhow_variant = "Unexpected"]
+    Database(DatabaseError),
+
+    Cache(CacheError),
+}
+
+#[test]
+fn overridden_inner_name() {
+    let error = AppError::from(DatabaseError::Unexpected(anyhow::Error));
+    assert!(matches!(error, AppError::Any(_)));
+
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn default_inner_name() {
+    let error = AppError::from(CacheError::Any(anyhow::Error));
+    assert!(matches!(error, AppError::Any(_)));
+
+    let error = AppError::from(CacheError::Miss);
+    assert!(matches!(error, AppError::Cache(CacheError::Miss)));
+}
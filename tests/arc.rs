@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug, Clone)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug)]
+struct IoError;
+
+#[derive(Debug, Clone, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[arc]
+    Database(Arc<DatabaseError>),
+
+    #[arc]
+    #[without_anyhow]
+    Io { source: Arc<IoError> },
+}
+
+#[test]
+fn converts_from_inner() {
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(ref inner) if matches!(**inner, DatabaseError::Timeout)));
+
+    let error = AppError::from(IoError);
+    assert!(matches!(error, AppError::Io { ref source } if matches!(**source, IoError)));
+}
+
+#[test]
+fn converts_from_arc() {
+    let shared = Arc::new(IoError);
+    let error = AppError::from(Arc::clone(&shared));
+    assert!(matches!(error, AppError::Io { ref source } if Arc::ptr_eq(source, &shared)));
+}
+
+#[test]
+fn extracts_anyhow() {
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn variant_stays_clone() {
+    let error = AppError::from(DatabaseError::Timeout);
+    let cloned = error.clone();
+
+    match (error, cloned) {
+        (AppError::Database(left), AppError::Database(right)) => assert!(Arc::ptr_eq(&left, &right)),
+        _ => panic!("expected two database errors"),
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "testing")]
+
+use error_conversion_macro::{assert_converts, ErrorEnum};
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum ApplicationError {
+    AnyhowError(anyhow::Error),
+    Invalid,
+}
+
+#[derive(Debug)]
+enum ErrorWithoutAnyhow {
+    SomeError,
+}
+
+#[derive(Debug)]
+struct DatabaseError;
+
+#[allow(clippy::enum_variant_names, dead_code)]
+#[derive(Debug, ErrorEnum)]
+enum Error {
+    AnyhowError(anyhow::Error),
+    ApplicationError(ApplicationError),
+
+    #[without_anyhow]
+    ErrorWithoutAnyhow(ErrorWithoutAnyhow),
+
+    #[without_anyhow]
+    DatabaseError { source: DatabaseError },
+}
+
+#[test]
+fn round_trip_of_every_variant() {
+    assert_converts!(anyhow::Error => Error::AnyhowError);
+    assert_converts!(ApplicationError::Invalid => Error::ApplicationError);
+    assert_converts!(ErrorWithoutAnyhow::SomeError => Error::ErrorWithoutAnyhow);
+    assert_converts!(DatabaseError => Error::DatabaseError);
+}
+
+#[test]
+fn extraction_round_trip() {
+    assert_converts!(ApplicationError::AnyhowError(anyhow::Error) => Error::AnyhowError);
+}
+
+#[test]
+#[should_panic(expected = "expected a conversion into `Error::ApplicationError`, got AnyhowError(Error)")]
+fn wrong_variant_panics() {
+    assert_converts!(ApplicationError::AnyhowError(anyhow::Error) => Error::ApplicationError);
+}
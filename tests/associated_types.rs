@@ -0,0 +1,52 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+trait Backend {
+    type Error;
+}
+
+struct Postgres;
+
+#[derive(Debug, PartialEq)]
+enum PostgresError {
+    Closed,
+}
+
+impl Backend for Postgres {
+    type Error = PostgresError;
+}
+
+struct Sqlite;
+
+#[derive(Debug, PartialEq)]
+struct SqliteError(i32);
+
+impl Backend for Sqlite {
+    type Error = SqliteError;
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Postgres(<Postgres as Backend>::Error),
+
+    #[wrap]
+    Sqlite { source: <Sqlite as Backend>::Error },
+}
+
+#[test]
+fn associated_type_is_wrapped() {
+    let error = AppError::from(PostgresError::Closed);
+    assert!(matches!(error, AppError::Postgres(PostgresError::Closed)));
+
+    let error = AppError::from(SqliteError(5));
+    assert!(matches!(error, AppError::Sqlite { source: SqliteError(5) }));
+
+    assert!(matches!(AppError::from(anyhow::Error), AppError::Anyhow(_)));
+}
@@ -0,0 +1,68 @@
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug)]
+struct ConfigError;
+
+mod derived {
+    use error_conversion_macro::ErrorEnum;
+
+    use super::{anyhow, ConfigError, DatabaseError};
+
+    #[derive(Debug, ErrorEnum)]
+    #[error_enum(predicates)]
+    pub enum AppError {
+        Anyhow(anyhow::Error),
+        Database(DatabaseError),
+
+        #[without_anyhow]
+        Config { #[from] source: ConfigError, retries: u8 },
+    }
+}
+
+mod attribute {
+    use error_conversion_macro::error_enum;
+
+    use super::{anyhow, ConfigError, DatabaseError};
+
+    #[error_enum(predicates)]
+    #[derive(Debug)]
+    pub enum AppError {
+        Anyhow(anyhow::Error),
+        Database(DatabaseError),
+
+        #[without_anyhow]
+        Config { #[from] source: ConfigError, retries: u8 },
+    }
+}
+
+#[test]
+fn same_conversions_as_derive() {
+    let derived = derived::AppError::from(DatabaseError::Anyhow(anyhow::Error));
+    let attribute = attribute::AppError::from(DatabaseError::Anyhow(anyhow::Error));
+    assert!(derived.is_anyhow() && attribute.is_anyhow());
+
+    let derived = derived::AppError::from(DatabaseError::Timeout);
+    let attribute = attribute::AppError::from(DatabaseError::Timeout);
+    assert!(matches!(derived, derived::AppError::Database(DatabaseError::Timeout)));
+    assert!(matches!(attribute, attribute::AppError::Database(DatabaseError::Timeout)));
+
+    let derived = derived::AppError::from(ConfigError);
+    let attribute = attribute::AppError::from(ConfigError);
+    assert!(matches!(derived, derived::AppError::Config { source: ConfigError, retries: 0 }));
+    assert!(matches!(attribute, attribute::AppError::Config { source: ConfigError, retries: 0 }));
+}
+
+#[test]
+fn aggregate_conversion() {
+    let attribute = attribute::AppError::from(anyhow::Error);
+    assert!(attribute.is_anyhow());
+}
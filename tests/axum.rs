@@ -0,0 +1,89 @@
+#![cfg(feature = "axum")]
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+/// Stand-in for the parts of axum the generated impl uses.
+mod axum {
+    pub mod http {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct StatusCode(pub u16);
+
+        impl StatusCode {
+            pub const BAD_REQUEST: StatusCode = StatusCode(400);
+            pub const INTERNAL_SERVER_ERROR: StatusCode = StatusCode(500);
+
+            pub fn from_u16(code: u16) -> Result<StatusCode, ()> {
+                Ok(StatusCode(code))
+            }
+        }
+    }
+
+    pub mod response {
+        use super::http::StatusCode;
+
+        #[derive(Debug)]
+        pub struct Response {
+            pub status: StatusCode,
+            pub body: String,
+        }
+
+        pub trait IntoResponse {
+            fn into_response(self) -> Response;
+        }
+
+        impl IntoResponse for (StatusCode, String) {
+            fn into_response(self) -> Response {
+                Response { status: self.0, body: self.1 }
+            }
+        }
+    }
+}
+
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+
+#[derive(Debug)]
+struct ValidationError;
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(into_response)]
+enum AppError {
+    #[error("unexpected")]
+    Anyhow(anyhow::Error),
+
+    #[status(StatusCode::BAD_REQUEST)]
+    #[without_anyhow]
+    #[error("invalid input")]
+    Validation(ValidationError),
+
+    #[status(404)]
+    #[without_anyhow]
+    #[error("{0} not found")]
+    NotFound(String),
+}
+
+#[test]
+fn numeric_status() {
+    let response = AppError::NotFound(String::from("user")).into_response();
+    assert_eq!(response.status, StatusCode(404));
+    assert_eq!(response.body, "user not found");
+}
+
+#[test]
+fn named_status() {
+    let response = AppError::from(ValidationError).into_response();
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.body, "invalid input");
+}
+
+#[test]
+fn aggregate_defaults_to_internal_server_error() {
+    let response = AppError::from(anyhow::Error).into_response();
+    assert_eq!(response.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.body, "unexpected");
+}
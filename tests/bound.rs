@@ -0,0 +1,64 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(String);
+
+    impl<E: std::error::Error> From<E> for Error {
+        fn from(error: E) -> Self {
+            Error(error.to_string())
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Rejected<T>(T);
+
+#[derive(Debug)]
+struct QueueFull;
+
+impl fmt::Display for QueueFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("queue is full")
+    }
+}
+
+impl std::error::Error for QueueFull {}
+
+// Only the conversion into the aggregate needs `E` to be an error, the enum itself holds any `E`
+#[derive(Debug, ErrorEnum)]
+enum QueueError<T, E> {
+    Any(anyhow::Error),
+
+    #[into_anyhow]
+    #[bound("E: std::error::Error + Send + Sync + 'static")]
+    Backend(E),
+
+    #[without_anyhow]
+    Rejected(Rejected<T>),
+}
+
+#[test]
+fn bound_applies_to_the_conversion() {
+    let error = QueueError::<u32, QueueFull>::from(QueueFull);
+    assert!(matches!(error, QueueError::Any(inner) if inner.to_string() == "queue is full"));
+}
+
+#[test]
+fn enum_holds_types_without_the_bound() {
+    let error = QueueError::<u32, String>::from(Rejected(7));
+    assert!(matches!(error, QueueError::Rejected(Rejected(7))));
+
+    let error = QueueError::<u32, String>::Backend(String::from("not an error"));
+    assert!(matches!(error, QueueError::Backend(message) if message == "not an error"));
+}
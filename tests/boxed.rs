@@ -0,0 +1,53 @@
+use std::mem::size_of;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+enum LargeError {
+    Anyhow(anyhow::Error),
+    Report([u8; 1024]),
+}
+
+#[derive(Debug)]
+struct LargeConfigError([u8; 1024]);
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[boxed]
+    Large(Box<LargeError>),
+
+    #[boxed]
+    #[without_anyhow]
+    Config { source: Box<LargeConfigError> },
+}
+
+#[test]
+fn enum_stays_small() {
+    assert!(size_of::<AppError>() <= 2 * size_of::<Box<LargeError>>());
+}
+
+#[test]
+fn boxed_conversion() {
+    let error = AppError::from(LargeError::Report([1; 1024]));
+    assert!(matches!(error, AppError::Large(inner) if matches!(*inner, LargeError::Report([1, ..]))));
+}
+
+#[test]
+fn boxed_conversion_extracts_anyhow() {
+    let error = AppError::from(LargeError::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn boxed_without_anyhow() {
+    let error = AppError::from(LargeConfigError([2; 1024]));
+    assert!(matches!(error, AppError::Config { source } if source.0[0] == 2));
+}
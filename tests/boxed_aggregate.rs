@@ -0,0 +1,46 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(pub &'static str);
+
+    impl std::fmt::Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Error {}
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    #[without_anyhow]
+    Timeout(std::time::Duration),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(Box<anyhow::Error>),
+    Database(DatabaseError),
+}
+
+#[test]
+fn aggregate_converts_into_box() {
+    let error = AppError::from(anyhow::Error("unexpected"));
+    assert!(matches!(error, AppError::Anyhow(inner) if inner.0 == "unexpected"));
+}
+
+#[test]
+fn extracted_aggregate_is_boxed() {
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error("connection lost")));
+    assert!(matches!(error, AppError::Anyhow(inner) if inner.0 == "connection lost"));
+}
+
+#[test]
+fn other_variants_are_wrapped() {
+    let error = AppError::from(DatabaseError::Timeout(std::time::Duration::from_secs(1)));
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout(_))));
+}
@@ -0,0 +1,61 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(pub String);
+}
+
+#[derive(Debug, PartialEq)]
+struct Timeout {
+    seconds: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct ParseError {
+    line: usize,
+}
+
+fn wrap(timeout: Timeout) -> anyhow::Error {
+    anyhow::Error(format!("timed out after {}s", timeout.seconds))
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Any(anyhow::Error),
+
+    #[build_with(|e| if e.seconds == 0 { AppError::Timeout(e) } else { AppError::Any(wrap(e)) })]
+    Timeout(Timeout),
+
+    #[without_anyhow]
+    #[build_with(move |e: ParseError| Self::Parse(ParseError { line: e.line + 1 }))]
+    Parse(ParseError),
+
+    #[boxed]
+    #[without_anyhow]
+    #[build_with(|value| AppError::Dropped(Box::new(value.saturating_add(1))))]
+    Dropped(Box<u8>),
+}
+
+#[test]
+fn closure_builds_another_variant() {
+    let error = AppError::from(Timeout { seconds: 30 });
+    assert!(matches!(error, AppError::Any(anyhow::Error(message)) if message == "timed out after 30s"));
+
+    let error = AppError::from(Timeout { seconds: 0 });
+    assert!(matches!(error, AppError::Timeout(Timeout { seconds: 0 })));
+}
+
+#[test]
+fn closure_with_typed_argument() {
+    fn parse() -> Result<(), AppError> {
+        Err(ParseError { line: 4 })?
+    }
+
+    assert!(matches!(parse(), Err(AppError::Parse(ParseError { line: 5 }))));
+}
+
+#[test]
+fn closure_receives_unboxed_value() {
+    let error = AppError::from(7u8);
+    assert!(matches!(error, AppError::Dropped(value) if *value == 8));
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(String);
+
+    impl<E: std::error::Error> From<E> for Error {
+        fn from(error: E) -> Self {
+            Error(error.to_string())
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct LibError;
+
+impl fmt::Display for LibError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("library failed")
+    }
+}
+
+impl std::error::Error for LibError {}
+
+#[derive(Debug)]
+struct OtherError {
+    code: u16,
+}
+
+impl fmt::Display for OtherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "other library failed with {}", self.code)
+    }
+}
+
+impl std::error::Error for OtherError {}
+
+#[derive(Debug, PartialEq)]
+struct DatabaseError;
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(catch_all_into_anyhow(LibError, OtherError))]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Database(DatabaseError),
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(catch_all_into_anyhow(LibError))]
+enum BoxedError {
+    Anyhow(Box<anyhow::Error>),
+}
+
+#[test]
+fn listed_types_convert_into_aggregate() {
+    let error = AppError::from(LibError);
+    assert!(matches!(error, AppError::Anyhow(inner) if inner.to_string() == "library failed"));
+
+    let error = AppError::from(OtherError { code: 3 });
+    assert!(matches!(error, AppError::Anyhow(inner) if inner.to_string() == "other library failed with 3"));
+}
+
+#[test]
+fn variants_still_convert() {
+    let error = AppError::from(DatabaseError);
+    assert!(matches!(error, AppError::Database(DatabaseError)));
+}
+
+#[test]
+fn boxed_aggregate() {
+    let BoxedError::Anyhow(inner) = BoxedError::from(LibError);
+    assert_eq!(inner.to_string(), "library failed");
+}
+
+#[test]
+fn question_mark_converts() {
+    fn call_library() -> Result<(), AppError> {
+        Err(LibError)?
+    }
+
+    assert!(matches!(call_library(), Err(AppError::Anyhow(_))));
+}
@@ -0,0 +1,83 @@
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+struct IoError;
+
+#[cfg(test)]
+#[derive(Debug)]
+struct CacheError;
+
+// Stands in for a type behind a disabled feature
+#[cfg(not(test))]
+struct DbError;
+
+mod derived {
+    use error_conversion_macro::ErrorEnum;
+
+    use super::{anyhow, IoError};
+
+    #[derive(Debug, ErrorEnum)]
+    pub enum AppError {
+        Anyhow(anyhow::Error),
+
+        #[without_anyhow]
+        Io(IoError),
+
+        #[cfg(test)]
+        #[without_anyhow]
+        Cache(super::CacheError),
+
+        #[cfg(not(test))]
+        #[without_anyhow]
+        Db(super::DbError),
+    }
+}
+
+mod attribute {
+    use error_conversion_macro::error_enum;
+
+    use super::{anyhow, IoError};
+
+    #[error_enum]
+    #[derive(Debug)]
+    pub enum AppError {
+        Anyhow(anyhow::Error),
+
+        #[without_anyhow]
+        Io(IoError),
+
+        #[cfg(test)]
+        #[without_anyhow]
+        Cache(super::CacheError),
+
+        #[cfg(not(test))]
+        #[without_anyhow]
+        Db(super::DbError),
+
+        // Excludes `Cache`, so both may convert from `CacheError`
+        #[cfg(not(test))]
+        #[without_anyhow]
+        Legacy(super::CacheError),
+    }
+}
+
+#[test]
+fn enabled_variant_converts() {
+    let error = derived::AppError::from(CacheError);
+    assert!(matches!(error, derived::AppError::Cache(CacheError)));
+
+    let error = attribute::AppError::from(CacheError);
+    assert!(matches!(error, attribute::AppError::Cache(CacheError)));
+}
+
+#[test]
+fn ungated_variants_convert() {
+    let error = attribute::AppError::from(IoError);
+    assert!(matches!(error, attribute::AppError::Io(IoError)));
+
+    let error = attribute::AppError::from(anyhow::Error);
+    assert!(matches!(error, attribute::AppError::Anyhow(_)));
+}
@@ -0,0 +1,52 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+struct Timeout {
+    seconds: u64,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(const_ctors)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Timeout(Timeout),
+
+    #[without_anyhow]
+    Message { source: String },
+
+    #[without_anyhow]
+    Type(u8),
+
+    NotFound,
+
+    #[skip_from]
+    Pair(u8, u8),
+}
+
+const NOT_FOUND: AppError = AppError::not_found();
+const TIMEOUT: AppError = AppError::timeout(Timeout { seconds: 30 });
+
+#[test]
+fn unit_variant_in_const() {
+    assert!(matches!(NOT_FOUND, AppError::NotFound));
+}
+
+#[test]
+fn copy_free_payload_in_const() {
+    assert!(matches!(TIMEOUT, AppError::Timeout(Timeout { seconds: 30 })));
+}
+
+#[test]
+fn runtime_constructors() {
+    assert!(matches!(AppError::message(String::from("failed")), AppError::Message { source } if source == "failed"));
+    assert!(matches!(AppError::r#type(3), AppError::Type(3)));
+    assert!(matches!(AppError::anyhow(anyhow::Error), AppError::Anyhow(_)));
+    assert!(matches!(AppError::Pair(1, 2), AppError::Pair(1, 2)));
+}
@@ -0,0 +1,34 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum BufferError<const N: usize> {
+    Anyhow(anyhow::Error),
+    Overflow([u8; N]),
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(predicates, kind)]
+enum MyError<const N: usize> {
+    Anyhow(anyhow::Error),
+
+    Buffer(BufferError<N>),
+}
+
+#[test]
+fn const_generic_inner() {
+    let error = MyError::from(BufferError::<4>::Overflow([1, 2, 3, 4]));
+    assert!(matches!(error, MyError::Buffer(BufferError::Overflow([1, 2, 3, 4]))));
+    assert!(error.is_buffer());
+    assert_eq!(error.kind(), MyErrorKind::Buffer);
+}
+
+#[test]
+fn const_generic_extraction() {
+    let error = MyError::from(BufferError::<8>::Anyhow(anyhow::Error));
+    assert!(matches!(error, MyError::<8>::Anyhow(_)));
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error {
+        pub context: Option<&'static str>,
+        pub source: Box<dyn std::error::Error + Send + Sync>,
+    }
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error { context: None, source: Box::new(error) }
+        }
+
+        pub fn context(self, context: &'static str) -> Self {
+            Error { context: Some(context), ..self }
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.context {
+                Some(context) => write!(f, "{}: {}", context, self.source),
+                None => fmt::Display::fmt(&self.source, f),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PluginError;
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("missing entry point")
+    }
+}
+
+impl std::error::Error for PluginError {}
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+    #[context("while loading plugin")]
+    Plugin(PluginError),
+}
+
+#[test]
+fn context_converts_into_aggregate() {
+    let error = AppError::from(PluginError);
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn context_is_formatted() {
+    let AppError::Anyhow(error) = AppError::from(PluginError) else {
+        panic!("expected the aggregate variant");
+    };
+    assert_eq!(error.to_string(), "while loading plugin: missing entry point");
+}
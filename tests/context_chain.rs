@@ -0,0 +1,72 @@
+use error_conversion_macro::ErrorEnum;
+
+// Like anyhow, context is attached in layers on top of the original message
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(Vec<String>);
+
+    impl Error {
+        pub fn msg(message: &str) -> Self {
+            Error(vec![message.to_string()])
+        }
+
+        pub fn context(mut self, context: &str) -> Self {
+            self.0.insert(0, context.to_string());
+            self
+        }
+
+        pub fn chain(&self) -> impl Iterator<Item = &str> {
+            self.0.iter().map(String::as_str)
+        }
+    }
+}
+
+#[derive(Debug)]
+enum StorageError {
+    Anyhow(anyhow::Error),
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+enum RepositoryError {
+    Anyhow(anyhow::Error),
+    Storage(StorageError),
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+enum ServiceError {
+    Anyhow(anyhow::Error),
+    Repository(RepositoryError),
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+enum BoxedServiceError {
+    Anyhow(Box<anyhow::Error>),
+    Repository(RepositoryError),
+}
+
+fn storage_failure() -> StorageError {
+    StorageError::Anyhow(anyhow::Error::msg("disk full").context("writing user"))
+}
+
+#[test]
+fn context_survives_two_extractions() {
+    let error = ServiceError::from(RepositoryError::from(storage_failure()));
+    let error = match error {
+        ServiceError::Anyhow(error) => error.context("saving profile"),
+        _ => panic!("expected the aggregate variant"),
+    };
+    assert_eq!(error.chain().collect::<Vec<_>>(), ["saving profile", "writing user", "disk full"]);
+}
+
+#[test]
+fn context_survives_boxing_the_aggregate() {
+    let error = BoxedServiceError::from(RepositoryError::from(storage_failure()));
+    let error = match error {
+        BoxedServiceError::Anyhow(error) => error,
+        _ => panic!("expected the aggregate variant"),
+    };
+    assert_eq!(error.chain().collect::<Vec<_>>(), ["writing user", "disk full"]);
+}
@@ -0,0 +1,82 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+// Holds a secret and deliberately has no Debug impl
+struct Password(String);
+
+#[derive(Debug)]
+struct Timeout {
+    seconds: u64,
+}
+
+// As with the derive, fields only read by Debug count as unused
+#[allow(dead_code)]
+#[derive(ErrorEnum)]
+#[error_enum(debug)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Timeout(Timeout),
+
+    #[redact]
+    #[without_anyhow]
+    Login(Password),
+
+    #[redact]
+    #[skip_from]
+    Token { user: u32, token: String },
+
+    #[skip_from]
+    Query { table: &'static str, f: u8 },
+
+    NotFound,
+}
+
+// As with the derive, fields only read by Debug count as unused
+#[allow(dead_code)]
+#[derive(ErrorEnum)]
+#[error_enum(debug)]
+enum GenericError<T> {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Rejected(Vec<T>),
+}
+
+#[test]
+fn redacted_variant_omits_payload() {
+    let error = AppError::from(Password(String::from("hunter2")));
+    let debug = format!("{:?}", error);
+    assert_eq!(debug, "Login(<redacted>)");
+    assert!(!debug.contains("hunter2"));
+    assert!(matches!(error, AppError::Login(Password(password)) if password == "hunter2"));
+
+    let error = AppError::Token { user: 7, token: String::from("secret") };
+    assert_eq!(format!("{:?}", error), "Token { user: <redacted>, token: <redacted> }");
+}
+
+#[test]
+fn other_variants_format_like_derive() {
+    let error = AppError::from(Timeout { seconds: 3 });
+    assert_eq!(format!("{:?}", error), "Timeout(Timeout { seconds: 3 })");
+    assert!(matches!(error, AppError::Timeout(timeout) if timeout.seconds == 3));
+    assert_eq!(format!("{:?}", AppError::from(anyhow::Error)), "Anyhow(Error)");
+    assert_eq!(format!("{:?}", AppError::Query { table: "users", f: 1 }), r#"Query { table: "users", f: 1 }"#);
+    assert_eq!(format!("{:?}", AppError::NotFound), "NotFound");
+}
+
+#[test]
+fn alternate_format() {
+    let error = AppError::from(Password(String::from("hunter2")));
+    assert_eq!(format!("{:#?}", error), "Login(\n    <redacted>,\n)");
+}
+
+#[test]
+fn generic_enum() {
+    assert_eq!(format!("{:?}", GenericError::from(vec![1, 2])), "Rejected([1, 2])");
+}
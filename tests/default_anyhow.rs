@@ -0,0 +1,75 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(pub &'static str);
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Error {}
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            self
+        }
+    }
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("database error")
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(std_error)]
+enum AppError {
+    #[error("fatal")]
+    Fatal(anyhow::Error),
+
+    #[default_anyhow]
+    #[error("internal")]
+    Internal(anyhow::Error),
+
+    #[error("database")]
+    #[anyhow_variant = "Anyhow"]
+    Database(DatabaseError),
+}
+
+#[test]
+fn aggregate_converts_into_default() {
+    let error = AppError::from(anyhow::Error("unexpected"));
+    assert!(matches!(error, AppError::Internal(_)));
+}
+
+#[test]
+fn extraction_uses_default() {
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error("connection lost")));
+    assert!(matches!(error, AppError::Internal(anyhow::Error("connection lost"))));
+
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn other_aggregate_variant_is_a_source() {
+    let error = AppError::Fatal(anyhow::Error("disk full"));
+    assert_eq!(error.source().unwrap().to_string(), "disk full");
+}
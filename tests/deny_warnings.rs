@@ -0,0 +1,55 @@
+#![deny(warnings)]
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+// Holds nothing but the aggregate, so the wildcard of the extraction is unreachable
+#[derive(Debug)]
+enum PoolError {
+    Anyhow(anyhow::Error),
+}
+
+#[derive(Debug, PartialEq)]
+enum CacheError {
+    Evicted,
+    Corrupted,
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+    Pool(PoolError),
+}
+
+// Every inner variant is lifted, so the fallback arm is unreachable
+#[derive(Debug, ErrorEnum)]
+#[error_enum(accessors, into_inner)]
+enum CacheAppError {
+    Anyhow(anyhow::Error),
+
+    #[flatten(Evicted, Corrupted)]
+    Cache(CacheError),
+
+    Evicted,
+    Corrupted,
+}
+
+#[test]
+fn single_variant_inner_enum() {
+    let error = AppError::from(PoolError::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn fully_flattened_inner_enum() {
+    assert!(matches!(CacheAppError::from(CacheError::Evicted), CacheAppError::Evicted));
+    assert!(matches!(CacheAppError::from(CacheError::Corrupted), CacheAppError::Corrupted));
+
+    let error = CacheAppError::Cache(CacheError::Corrupted);
+    assert_eq!(error.as_cache(), Some(&CacheError::Corrupted));
+    assert!(error.into_anyhow().is_err());
+}
@@ -0,0 +1,31 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+struct LegacyError;
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    #[deprecated_from("convert into ConfigError instead")]
+    Legacy(LegacyError),
+}
+
+#[test]
+fn deprecated_conversion_still_works() {
+    let error = AppError::from(LegacyError);
+    assert!(matches!(error, AppError::Legacy(LegacyError)));
+}
+
+#[test]
+#[allow(deprecated)]
+fn constructor_converts_like_from() {
+    let error = AppError::from_legacy(LegacyError);
+    assert!(matches!(error, AppError::Legacy(LegacyError)));
+}
@@ -0,0 +1,104 @@
+use std::error::Error as _;
+use std::fmt;
+use std::ops::Deref;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(pub &'static str);
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Error {}
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            self
+        }
+    }
+}
+
+/// Transparent newtype around the aggregate, as some codebases use.
+#[derive(Debug)]
+struct AppErr(pub anyhow::Error);
+
+impl From<anyhow::Error> for AppErr {
+    fn from(error: anyhow::Error) -> Self {
+        AppErr(error)
+    }
+}
+
+impl Deref for AppErr {
+    type Target = anyhow::Error;
+
+    fn deref(&self) -> &anyhow::Error {
+        &self.0
+    }
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("database error")
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(std_error)]
+enum AppError {
+    #[deref_anyhow]
+    #[error("unexpected")]
+    Anyhow(AppErr),
+
+    #[error("database")]
+    Database(DatabaseError),
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(into_aggregate)]
+enum ServiceError {
+    #[deref_anyhow]
+    Anyhow(AppErr),
+}
+
+#[test]
+fn aggregate_converts_into_newtype() {
+    let error = AppError::from(anyhow::Error("unexpected"));
+    assert!(matches!(error, AppError::Anyhow(AppErr(anyhow::Error("unexpected")))));
+}
+
+#[test]
+fn extracted_aggregate_is_wrapped_in_newtype() {
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error("connection lost")));
+    assert!(matches!(error, AppError::Anyhow(AppErr(anyhow::Error("connection lost")))));
+
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn source_derefs_through_newtype() {
+    let error = AppError::from(anyhow::Error("unexpected"));
+    assert_eq!(error.source().unwrap().to_string(), "unexpected");
+}
+
+#[test]
+fn into_aggregate_unwraps_newtype() {
+    let error = ServiceError::from(anyhow::Error("unexpected"));
+    let aggregate: anyhow::Error = error.into();
+    assert_eq!(aggregate.0, "unexpected");
+}
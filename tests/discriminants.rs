@@ -0,0 +1,53 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+struct IoError;
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(predicates, accessors, into_inner, kind)]
+#[repr(u8)]
+enum AppError {
+    Anyhow(anyhow::Error) = 1,
+
+    #[without_anyhow]
+    Io(IoError) = 2,
+
+    Database(DatabaseError) = 4,
+
+    NotFound = 8,
+}
+
+#[test]
+fn conversions_ignore_discriminants() {
+    assert!(matches!(AppError::from(IoError), AppError::Io(IoError)));
+    assert!(matches!(AppError::from(DatabaseError::Timeout), AppError::Database(DatabaseError::Timeout)));
+    assert!(matches!(AppError::from(DatabaseError::Anyhow(anyhow::Error)), AppError::Anyhow(_)));
+}
+
+#[test]
+fn helpers_ignore_discriminants() {
+    let error = AppError::from(IoError);
+    assert!(error.is_io());
+    assert_eq!(error.as_io(), Some(&IoError));
+    assert!(AppError::NotFound.is_not_found());
+    assert!(matches!(error.into_io(), Ok(IoError)));
+}
+
+// The kind enum mirrors the variants without their discriminants, so its own are implicit
+#[test]
+fn kind_has_no_discriminants() {
+    assert_eq!(AppError::NotFound.kind(), AppErrorKind::NotFound);
+    assert_eq!(AppErrorKind::Anyhow as u8, 0);
+    assert_eq!(AppErrorKind::NotFound as u8, 3);
+}
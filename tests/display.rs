@@ -0,0 +1,85 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("anyhow error")
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ConfigError;
+
+#[derive(Debug)]
+struct QueryError;
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    #[error("failed to read config: {0:?}")]
+    Config(ConfigError),
+
+    #[without_anyhow]
+    #[error("query failed: {source:?} {{retry}}")]
+    Query { source: QueryError },
+
+    #[without_anyhow]
+    #[error("bad request: {0}")]
+    BadRequest(String),
+
+    #[error("timed out")]
+    Timeout,
+
+    #[skip_from]
+    #[error("rate limited: {} of {}")]
+    RateLimited(u32, u32),
+
+    NotFound,
+}
+
+#[test]
+fn tuple_field_message() {
+    let error = AppError::from(ConfigError);
+    assert_eq!(error.to_string(), "failed to read config: ConfigError");
+}
+
+#[test]
+fn named_field_message() {
+    let error = AppError::from(QueryError);
+    assert_eq!(error.to_string(), "query failed: QueryError {retry}");
+}
+
+#[test]
+fn plain_tuple_field_message() {
+    let error = AppError::from(String::from("missing id"));
+    assert_eq!(error.to_string(), "bad request: missing id");
+}
+
+#[test]
+fn implicit_placeholders_take_fields_in_order() {
+    assert_eq!(AppError::RateLimited(3, 10).to_string(), "rate limited: 3 of 10");
+}
+
+#[test]
+fn message_without_arguments() {
+    assert_eq!(AppError::Timeout.to_string(), "timed out");
+}
+
+#[test]
+fn falls_back_to_inner_display() {
+    let error = AppError::from(anyhow::Error);
+    assert_eq!(error.to_string(), "anyhow error");
+}
+
+#[test]
+fn unit_variant_displays_name() {
+    assert_eq!(AppError::NotFound.to_string(), "NotFound");
+}
@@ -0,0 +1,84 @@
+use std::{fmt, io};
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(Box<dyn std::error::Error + Send + Sync>);
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(Box::new(error))
+        }
+
+        pub fn downcast<E: std::error::Error + Send + Sync + 'static>(self) -> Result<E, Self> {
+            self.0.downcast().map(|error| *error).map_err(Error)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("parse error")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timeout")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(downcast_from_anyhow)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    Io(io::Error),
+
+    #[wrap]
+    #[boxed]
+    Parse(Box<ParseError>),
+
+    #[without_anyhow]
+    Message(String),
+}
+
+#[test]
+fn hidden_io_error_is_recovered() {
+    let error = AppError::from(anyhow::Error::new(io::Error::other("disk full")));
+    assert!(matches!(error, AppError::Io(error) if error.to_string() == "disk full"));
+}
+
+#[test]
+fn boxed_variant_is_recovered() {
+    let error = AppError::from(anyhow::Error::new(ParseError));
+    assert!(matches!(error, AppError::Parse(error) if *error == ParseError));
+}
+
+#[test]
+fn unknown_error_stays_aggregate() {
+    let error = AppError::from(anyhow::Error::new(Timeout));
+    let error = match error {
+        AppError::Anyhow(error) => error,
+        _ => panic!("expected the aggregate variant"),
+    };
+    assert!(error.downcast::<Timeout>().is_ok());
+}
+
+#[test]
+fn direct_conversions_are_kept() {
+    let error = AppError::from(String::from("invalid"));
+    assert!(matches!(error, AppError::Message(message) if message == "invalid"));
+}
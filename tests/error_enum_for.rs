@@ -0,0 +1,78 @@
+use error_conversion_macro::error_enum_for;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug)]
+struct ConfigError;
+
+#[derive(Debug)]
+enum AppError {
+    Anyhow(anyhow::Error),
+    Database(DatabaseError),
+    Config(ConfigError),
+}
+
+#[error_enum_for(AppError)]
+mod app_error {
+    #[error_enum(predicates)]
+    enum AppError {
+        Anyhow(anyhow::Error),
+        Database(DatabaseError),
+
+        #[without_anyhow]
+        Config(ConfigError),
+    }
+}
+
+mod store {
+    #[derive(Debug)]
+    pub enum StoreError {
+        Anyhow(crate::anyhow::Error),
+        Database(crate::DatabaseError),
+    }
+}
+
+// The copy may be named differently from the enum it describes
+#[error_enum_for(store::StoreError)]
+mod store_error {
+    enum Store {
+        Anyhow(anyhow::Error),
+        Database(DatabaseError),
+    }
+}
+
+#[test]
+fn conversions_for_enum_defined_elsewhere() {
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+
+    let error = AppError::from(ConfigError);
+    assert!(matches!(error, AppError::Config(ConfigError)));
+}
+
+#[test]
+fn methods_for_enum_defined_elsewhere() {
+    assert!(AppError::from(ConfigError).is_config());
+    assert!(!AppError::from(anyhow::Error).is_database());
+}
+
+#[test]
+fn enum_behind_path() {
+    let error = store::StoreError::from(DatabaseError::Anyhow(anyhow::Error));
+    assert!(matches!(error, store::StoreError::Anyhow(_)));
+
+    let error = store::StoreError::from(DatabaseError::Timeout);
+    assert!(matches!(error, store::StoreError::Database(DatabaseError::Timeout)));
+}
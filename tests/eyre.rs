@@ -0,0 +1,78 @@
+#![cfg(feature = "eyre")]
+
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod eyre {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Report {
+        pub message: String,
+    }
+
+    impl Report {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Report { message: error.to_string() }
+        }
+
+        pub fn wrap_err(self, message: &'static str) -> Self {
+            Report { message: format!("{}: {}", message, self.message) }
+        }
+    }
+
+    impl fmt::Display for Report {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.message)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PluginError;
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("missing entry point")
+    }
+}
+
+impl std::error::Error for PluginError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "eyre::Report")]
+enum DatabaseError {
+    Report(eyre::Report),
+    #[allow(dead_code)]
+    #[without_anyhow]
+    Timeout(std::time::Duration),
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "eyre::Report")]
+enum AppError {
+    Report(eyre::Report),
+    Database(DatabaseError),
+    #[context("while loading plugin")]
+    Plugin(PluginError),
+}
+
+#[test]
+fn report_is_the_aggregate() {
+    let error = AppError::from(eyre::Report { message: String::from("unexpected") });
+    assert!(matches!(error, AppError::Report(_)));
+}
+
+#[test]
+fn report_is_extracted() {
+    let error = AppError::from(DatabaseError::Report(eyre::Report { message: String::from("connection lost") }));
+    assert!(matches!(error, AppError::Report(report) if report.message == "connection lost"));
+}
+
+#[test]
+fn context_uses_wrap_err() {
+    let error = AppError::from(PluginError);
+    assert!(matches!(error, AppError::Report(report) if report.message == "while loading plugin: missing entry point"));
+}
@@ -0,0 +1,100 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+struct Timeout(u64);
+
+#[derive(Debug, PartialEq)]
+enum DatabaseError {
+    NotFound,
+    Timeout(Timeout),
+    Closed,
+}
+
+#[derive(Debug, PartialEq)]
+enum CacheError {
+    Evicted { key: String },
+    Corrupted,
+}
+
+// Shaped like an enum of another crate, which only lets other crates match on it with a wildcard arm
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+enum StorageError {
+    NotFound,
+    Timeout(Timeout),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[flatten(NotFound, Timeout)]
+    Database(DatabaseError),
+
+    #[flatten(Evicted)]
+    Cache(CacheError),
+
+    #[without_anyhow]
+    Timeout(Timeout),
+
+    NotFound,
+
+    #[skip_from]
+    Evicted { key: String },
+}
+
+#[test]
+fn unit_variant_is_lifted() {
+    let error = AppError::from(DatabaseError::NotFound);
+    assert!(matches!(error, AppError::NotFound));
+}
+
+#[test]
+fn tuple_variant_is_lifted() {
+    let error = AppError::from(DatabaseError::Timeout(Timeout(30)));
+    assert!(matches!(error, AppError::Timeout(Timeout(30))));
+}
+
+#[test]
+fn named_variant_is_lifted() {
+    let error = AppError::from(CacheError::Evicted { key: String::from("session") });
+    assert!(matches!(error, AppError::Evicted { key } if key == "session"));
+}
+
+#[test]
+fn unlisted_variant_is_nested() {
+    let error = AppError::from(CacheError::Corrupted);
+    assert!(matches!(error, AppError::Cache(CacheError::Corrupted)));
+
+    let error = AppError::from(DatabaseError::Closed);
+    assert!(matches!(error, AppError::Database(DatabaseError::Closed)));
+}
+
+#[derive(Debug, ErrorEnum)]
+enum StorageAppError {
+    Anyhow(anyhow::Error),
+
+    // Every inner variant is lifted, so the field is never read
+    #[allow(dead_code)]
+    #[flatten(NotFound, Timeout)]
+    Storage(StorageError),
+
+    #[without_anyhow]
+    Timeout(Timeout),
+
+    NotFound,
+}
+
+#[test]
+fn non_exhaustive_inner_enum() {
+    let error = StorageAppError::from(StorageError::NotFound);
+    assert!(matches!(error, StorageAppError::NotFound));
+
+    let error = StorageAppError::from(StorageError::Timeout(Timeout(5)));
+    assert!(matches!(error, StorageAppError::Timeout(Timeout(5))));
+}
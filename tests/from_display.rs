@@ -0,0 +1,56 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+struct LimitExceeded {
+    limit: u32,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "limit of {} exceeded", self.limit)
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    #[from_display(LimitExceeded, std::num::ParseIntError)]
+    Invalid(String),
+
+    #[skip_from]
+    #[from_display(std::fmt::Error)]
+    Format { message: String },
+}
+
+#[test]
+fn display_type_becomes_message() {
+    let error = AppError::from(LimitExceeded { limit: 10 });
+    assert!(matches!(error, AppError::Invalid(message) if message == "limit of 10 exceeded"));
+}
+
+#[test]
+fn several_types_are_listed() {
+    let error = AppError::from("ten".parse::<u32>().unwrap_err());
+    assert!(matches!(error, AppError::Invalid(message) if message == "invalid digit found in string"));
+}
+
+#[test]
+fn variant_field_is_kept() {
+    let error = AppError::from(String::from("invalid input"));
+    assert!(matches!(error, AppError::Invalid(message) if message == "invalid input"));
+}
+
+#[test]
+fn named_field() {
+    let error = AppError::from(fmt::Error);
+    assert!(matches!(error, AppError::Format { message } if message == "an error occurred when formatting an argument"));
+}
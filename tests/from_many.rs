@@ -0,0 +1,68 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+struct ValidationError {
+    field: &'static str,
+}
+
+#[derive(Debug)]
+enum RowError {
+    Anyhow(anyhow::Error),
+    Malformed(usize),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    #[from_many]
+    Validation(Vec<ValidationError>),
+
+    #[from_many]
+    Import { rows: Vec<RowError> },
+}
+
+fn validate(field: &'static str) -> Result<(), ValidationError> {
+    Err(ValidationError { field })
+}
+
+fn save() -> Result<(), AppError> {
+    validate("email")?;
+    Ok(())
+}
+
+#[test]
+fn single_error_becomes_one_element() {
+    let error = save().unwrap_err();
+    assert!(matches!(error, AppError::Validation(errors) if errors == [ValidationError { field: "email" }]));
+}
+
+#[test]
+fn several_errors_are_collected() {
+    let error: AppError = ["email", "name"].into_iter().map(|field| ValidationError { field }).collect();
+    assert!(matches!(error, AppError::Validation(errors) if errors.len() == 2 && errors[1].field == "name"));
+
+    let error = AppError::from(vec![ValidationError { field: "age" }]);
+    assert!(matches!(error, AppError::Validation(errors) if errors[0].field == "age"));
+}
+
+#[test]
+fn named_field_collects() {
+    let error: AppError = (1..4).map(RowError::Malformed).collect();
+    assert!(matches!(error, AppError::Import { rows } if rows.len() == 3));
+}
+
+#[test]
+fn single_element_extracts_aggregate() {
+    let error = AppError::from(RowError::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+
+    let error = AppError::from(RowError::Malformed(7));
+    assert!(matches!(error, AppError::Import { rows } if matches!(rows[..], [RowError::Malformed(7)])));
+}
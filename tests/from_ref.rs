@@ -0,0 +1,50 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DatabaseError {
+    Timeout,
+    Refused,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StatusCode(u16);
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[from_ref]
+    #[without_anyhow]
+    Database(DatabaseError),
+
+    #[from_ref]
+    #[without_anyhow]
+    #[boxed]
+    Status(Box<StatusCode>),
+}
+
+#[test]
+fn converts_from_reference() {
+    let inner = DatabaseError::Timeout;
+    let error = AppError::from(&inner);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+    assert_eq!(inner, DatabaseError::Timeout);
+}
+
+#[test]
+fn converts_from_reference_into_box() {
+    let status = StatusCode(503);
+    let error = AppError::from(&status);
+    assert!(matches!(error, AppError::Status(inner) if *inner == StatusCode(503)));
+}
+
+#[test]
+fn owned_conversion_still_works() {
+    let error = AppError::from(DatabaseError::Refused);
+    assert!(matches!(error, AppError::Database(DatabaseError::Refused)));
+}
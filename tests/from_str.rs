@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+struct UnknownCode(String);
+
+#[derive(Debug, PartialEq)]
+struct Timeout {
+    seconds: u64,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(from_str(AppError::from_code, UnknownCode))]
+enum AppError {
+    Any(anyhow::Error),
+
+    #[without_anyhow]
+    Timeout(Timeout),
+
+    NotFound,
+}
+
+impl AppError {
+    fn from_code(code: &str) -> Result<Self, UnknownCode> {
+        match code {
+            "not_found" => Ok(AppError::NotFound),
+            "timeout" => Ok(AppError::from(Timeout { seconds: 30 })),
+            _ => Err(UnknownCode(code.to_string())),
+        }
+    }
+}
+
+#[test]
+fn code_parses_into_variant() {
+    assert!(matches!(AppError::from_str("not_found"), Ok(AppError::NotFound)));
+    assert!(matches!("timeout".parse::<AppError>(), Ok(AppError::Timeout(Timeout { seconds: 30 }))));
+    assert!(matches!(AppError::from(anyhow::Error), AppError::Any(_)));
+}
+
+#[test]
+fn unknown_code_is_the_error() {
+    assert_eq!("teapot".parse::<AppError>().unwrap_err(), UnknownCode(String::from("teapot")));
+}
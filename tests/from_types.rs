@@ -0,0 +1,69 @@
+use std::fmt;
+use std::num::ParseIntError;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(pub String);
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(error.to_string())
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid syntax")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    #[from_types(std::io::Error, fmt::Error, ParseIntError)]
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    #[from_types(Box<str>)]
+    Message(String),
+
+    #[without_anyhow]
+    Parse(ParseError),
+}
+
+#[test]
+fn io_error_converts() {
+    let error = AppError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "missing file"));
+    assert!(matches!(error, AppError::Anyhow(anyhow::Error(message)) if message == "missing file"));
+}
+
+#[test]
+fn fmt_error_converts() {
+    let error = AppError::from(fmt::Error);
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn parse_int_error_converts() {
+    let error = AppError::from("x".parse::<u8>().unwrap_err());
+    assert!(matches!(error, AppError::Anyhow(anyhow::Error(message)) if message == "invalid digit found in string"));
+}
+
+#[test]
+fn listed_type_converts_into_field() {
+    let error = AppError::from(Box::<str>::from("timed out"));
+    assert!(matches!(error, AppError::Message(message) if message == "timed out"));
+}
+
+#[test]
+fn own_inner_type_still_converts() {
+    let error = AppError::from(ParseError);
+    assert!(matches!(error, AppError::Parse(ParseError)));
+}
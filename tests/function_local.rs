@@ -0,0 +1,124 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(pub String);
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &Message
+        }
+    }
+
+    #[derive(Debug)]
+    struct Message;
+
+    impl fmt::Display for Message {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("message")
+        }
+    }
+
+    impl std::error::Error for Message {}
+}
+
+#[derive(Debug, PartialEq)]
+struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid syntax")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[test]
+fn enum_declared_in_function() {
+    #[derive(Debug)]
+    enum InnerError {
+        Anyhow(anyhow::Error),
+        Closed,
+    }
+
+    impl fmt::Display for InnerError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("inner failed")
+        }
+    }
+
+    impl std::error::Error for InnerError {}
+
+    #[derive(Debug, ErrorEnum)]
+    #[error_enum(std_error, predicates, accessors, into_inner, kind)]
+    enum LocalError {
+        #[error("unexpected: {0}")]
+        Anyhow(anyhow::Error),
+
+        #[wrap]
+        #[error("parse failed")]
+        Parse(ParseError),
+
+        #[error("inner")]
+        Inner(InnerError),
+
+        #[error("not found")]
+        NotFound,
+    }
+
+    let error = LocalError::from(ParseError);
+    assert!(error.is_parse());
+    assert_eq!(error.as_parse(), Some(&ParseError));
+    assert_eq!(error.kind(), LocalErrorKind::Parse);
+    assert_eq!(error.to_string(), "parse failed");
+    assert_eq!(error.source().unwrap().to_string(), "invalid syntax");
+    assert!(matches!(error.into_parse(), Ok(ParseError)));
+
+    let error = LocalError::from(InnerError::Anyhow(anyhow::Error(String::from("extracted"))));
+    assert!(matches!(error, LocalError::Anyhow(ref inner) if inner.0 == "extracted"));
+    assert_eq!(error.to_string(), "unexpected: extracted");
+
+    let error = LocalError::from(InnerError::Closed);
+    assert!(matches!(error, LocalError::Inner(InnerError::Closed)));
+    assert_eq!(LocalError::NotFound.kind(), LocalErrorKind::NotFound);
+}
+
+#[test]
+fn enums_of_the_same_name_in_two_functions() {
+    fn first() -> bool {
+        #[derive(Debug, ErrorEnum)]
+        #[error_enum(accessors)]
+        enum ScopedError {
+            Anyhow(anyhow::Error),
+
+            #[without_anyhow]
+            Parse(ParseError),
+        }
+
+        let error = ScopedError::from(ParseError);
+        error.as_parse().is_some() && error.as_anyhow().is_none()
+    }
+
+    fn second() -> bool {
+        #[derive(Debug, ErrorEnum)]
+        enum ScopedError {
+            Anyhow(anyhow::Error),
+        }
+
+        matches!(ScopedError::from(anyhow::Error(String::from("second"))), ScopedError::Anyhow(inner) if inner.0 == "second")
+    }
+
+    assert!(first());
+    assert!(second());
+}
@@ -0,0 +1,37 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum StoreError<K> {
+    Anyhow(anyhow::Error),
+    Missing(K),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+    Users(StoreError<String>),
+    Orders(StoreError<u64>),
+}
+
+#[test]
+fn generic_inner_is_wrapped() {
+    let error = AppError::from(StoreError::Missing(String::from("alice")));
+    assert!(matches!(error, AppError::Users(StoreError::Missing(key)) if key == "alice"));
+}
+
+#[test]
+fn instantiations_get_separate_impls() {
+    let error = AppError::from(StoreError::Missing(7u64));
+    assert!(matches!(error, AppError::Orders(StoreError::Missing(7))));
+}
+
+#[test]
+fn generic_inner_extracts_anyhow() {
+    let error = AppError::from(StoreError::<u64>::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
@@ -0,0 +1,56 @@
+use std::fmt::Debug;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+struct Wrapped<E>(E);
+
+#[derive(Debug)]
+struct ParseError;
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parse error")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, ErrorEnum)]
+enum GenericError<E> where E: std::error::Error {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Inner(Wrapped<E>),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum BoundedError<E: Debug> {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Inner(Wrapped<E>),
+}
+
+#[test]
+fn generic_variant() {
+    let error = GenericError::from(Wrapped(ParseError));
+    assert!(matches!(error, GenericError::Inner(Wrapped(ParseError))));
+}
+
+#[test]
+fn generic_aggregate() {
+    let error: GenericError<ParseError> = GenericError::from(anyhow::Error);
+    assert!(matches!(error, GenericError::Anyhow(_)));
+}
+
+#[test]
+fn inline_bound() {
+    let error = BoundedError::from(Wrapped(1u8));
+    assert!(matches!(error, BoundedError::Inner(Wrapped(1))));
+}
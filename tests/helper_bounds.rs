@@ -0,0 +1,57 @@
+use error_conversion_macro::ErrorEnum;
+
+// Neither the aggregate nor the payloads implement Debug, Clone or PartialEq
+mod anyhow {
+    pub struct Error;
+}
+
+struct Connection {
+    id: u32,
+}
+
+struct PoolError(Connection);
+
+#[derive(ErrorEnum)]
+#[error_enum(predicates, accessors, into_inner, kind)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Pool(PoolError),
+
+    #[without_anyhow]
+    Closed { source: Connection },
+
+    NotFound,
+}
+
+#[test]
+fn predicates_without_bounds() {
+    let error = AppError::from(PoolError(Connection { id: 1 }));
+    assert!(error.is_pool());
+    assert!(!error.is_closed());
+    assert!(!AppError::from(anyhow::Error).is_not_found());
+}
+
+#[test]
+fn accessors_without_bounds() {
+    let error = AppError::from(Connection { id: 2 });
+    assert_eq!(error.as_closed().map(|connection| connection.id), Some(2));
+    assert!(error.as_pool().is_none());
+}
+
+#[test]
+fn into_inner_without_bounds() {
+    let error = AppError::from(PoolError(Connection { id: 3 }));
+    let error = match error.into_closed() {
+        Ok(_) => panic!("expected the pool variant"),
+        Err(error) => error,
+    };
+    assert!(matches!(error.into_pool(), Ok(PoolError(Connection { id: 3 }))));
+}
+
+#[test]
+fn kind_without_bounds() {
+    assert_eq!(AppError::from(Connection { id: 4 }).kind(), AppErrorKind::Closed);
+    assert_eq!(AppError::NotFound.kind(), AppErrorKind::NotFound);
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(Box<dyn std::error::Error + Send + Sync>);
+
+    #[derive(Debug)]
+    struct Message(String);
+
+    impl fmt::Display for Message {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl std::error::Error for Message {}
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(Box::new(error))
+        }
+
+        pub fn msg<M: fmt::Display>(message: M) -> Self {
+            Error(Box::new(Message(message.to_string())))
+        }
+
+        pub fn downcast<E: std::error::Error + Send + Sync + 'static>(self) -> Result<E, Self> {
+            self.0.downcast().map(|error| *error).map_err(Error)
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseError::Anyhow(e) => fmt::Display::fmt(e, f),
+            DatabaseError::Timeout => f.write_str("database timed out"),
+        }
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(into_aggregate)]
+enum AppError {
+    Anyhow(anyhow::Error),
+    Database(DatabaseError),
+
+    #[without_anyhow]
+    Custom(String),
+
+    NotFound,
+}
+
+#[test]
+fn aggregate_round_trip() {
+    let error = AppError::from(anyhow::Error::msg("boom"));
+    let error = anyhow::Error::from(error);
+    assert_eq!(error.to_string(), "boom");
+
+    let error = AppError::from(error);
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn wrapped_error_round_trip() {
+    let error = AppError::from(DatabaseError::Timeout);
+    let error = anyhow::Error::from(error);
+    assert!(matches!(error.downcast::<DatabaseError>(), Ok(DatabaseError::Timeout)));
+}
+
+#[test]
+fn extracted_aggregate_round_trip() {
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error::msg("pool exhausted")));
+    let error = anyhow::Error::from(error);
+    assert_eq!(error.to_string(), "pool exhausted");
+}
+
+#[test]
+fn without_anyhow_becomes_message() {
+    let error = AppError::from(String::from("invalid input"));
+    let error = anyhow::Error::from(error);
+    assert_eq!(error.to_string(), "invalid input");
+}
+
+#[test]
+fn unit_variant_becomes_name() {
+    let error = anyhow::Error::from(AppError::NotFound);
+    assert_eq!(error.to_string(), "NotFound");
+}
@@ -0,0 +1,51 @@
+use std::io;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(pub String);
+
+    impl<E: std::error::Error> From<E> for Error {
+        fn from(error: E) -> Self {
+            Error(error.to_string())
+        }
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[into_anyhow]
+    Io(io::Error),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum BoxedError {
+    Anyhow(Box<anyhow::Error>),
+
+    #[into_anyhow]
+    Io(io::Error),
+}
+
+#[test]
+fn converts_into_aggregate() {
+    let error = AppError::from(io::Error::new(io::ErrorKind::NotFound, "config.toml"));
+    assert!(matches!(error, AppError::Anyhow(anyhow::Error(message)) if message == "config.toml"));
+}
+
+#[test]
+fn converts_into_boxed_aggregate() {
+    let error = BoxedError::from(io::Error::new(io::ErrorKind::NotFound, "config.toml"));
+    assert!(matches!(error, BoxedError::Anyhow(inner) if inner.0 == "config.toml"));
+}
+
+#[test]
+fn variant_can_still_be_constructed() {
+    let error = AppError::Io(io::Error::other("disk"));
+    assert!(matches!(error, AppError::Io(inner) if inner.kind() == io::ErrorKind::Other));
+
+    let error = BoxedError::Io(io::Error::other("disk"));
+    assert!(matches!(error, BoxedError::Io(inner) if inner.kind() == io::ErrorKind::Other));
+}
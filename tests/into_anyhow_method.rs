@@ -0,0 +1,103 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(Box<dyn std::error::Error + Send + Sync>);
+
+    #[derive(Debug)]
+    struct Message(String);
+
+    impl fmt::Display for Message {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl std::error::Error for Message {}
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(Box::new(error))
+        }
+
+        pub fn msg<M: fmt::Display>(message: M) -> Self {
+            Error(Box::new(Message(message.to_string())))
+        }
+
+        pub fn downcast<E: std::error::Error + Send + Sync + 'static>(self) -> Result<E, Self> {
+            self.0.downcast().map(|error| *error).map_err(Error)
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl AsRef<dyn std::error::Error + 'static> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &*self.0
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+// std_error can't be combined with into_aggregate, but with the method
+#[derive(Debug, ErrorEnum)]
+#[error_enum(into_anyhow_method, std_error)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    Timeout(Timeout),
+
+    #[without_anyhow]
+    Custom(String),
+
+    NotFound,
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[test]
+fn aggregate_is_returned() {
+    let error = AppError::from(anyhow::Error::msg("boom")).into_anyhow();
+    assert_eq!(error.to_string(), "boom");
+}
+
+#[test]
+fn wrapped_error_is_kept() {
+    let error = AppError::from(Timeout).into_anyhow();
+    assert!(error.downcast::<Timeout>().is_ok());
+}
+
+#[test]
+fn without_anyhow_becomes_message() {
+    let error = AppError::from(String::from("invalid input")).into_anyhow();
+    assert_eq!(error.to_string(), "invalid input");
+}
+
+#[test]
+fn unit_variant_becomes_its_name() {
+    let error = AppError::NotFound.into_anyhow();
+    assert_eq!(error.to_string(), "NotFound");
+}
@@ -0,0 +1,53 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+enum ApplicationError {
+    Invalid,
+}
+
+#[derive(Debug, PartialEq)]
+struct DatabaseError(String);
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(into_inner)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    ApplicationError(ApplicationError),
+
+    #[without_anyhow]
+    Database { source: DatabaseError },
+
+    NotFound,
+}
+
+#[test]
+fn tuple_into_inner() {
+    let error = AppError::from(ApplicationError::Invalid);
+    assert_eq!(error.into_application_error().unwrap(), ApplicationError::Invalid);
+}
+
+#[test]
+fn named_into_inner() {
+    let error = AppError::from(DatabaseError(String::from("connection lost")));
+    assert_eq!(error.into_database().unwrap(), DatabaseError(String::from("connection lost")));
+}
+
+#[test]
+fn mismatch_returns_ownership() {
+    let error = AppError::from(DatabaseError(String::from("connection lost")));
+    let error = error.into_application_error().unwrap_err();
+    let error = error.into_anyhow().unwrap_err();
+    assert!(matches!(error, AppError::Database { source } if source.0 == "connection lost"));
+}
+
+#[test]
+fn unit_variant_is_returned() {
+    assert!(matches!(AppError::NotFound.into_database(), Err(AppError::NotFound)));
+}
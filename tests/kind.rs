@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+struct DatabaseError;
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+#[error_enum(kind)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Database { source: DatabaseError },
+
+    #[without_anyhow]
+    Http(u16, #[from] String),
+
+    NotFound,
+}
+
+#[test]
+fn kind_of_every_shape() {
+    assert_eq!(AppError::from(anyhow::Error).kind(), AppErrorKind::Anyhow);
+    assert_eq!(AppError::from(DatabaseError).kind(), AppErrorKind::Database);
+    assert_eq!(AppError::from(String::from("bad gateway")).kind(), AppErrorKind::Http);
+    assert_eq!(AppError::NotFound.kind(), AppErrorKind::NotFound);
+}
+
+#[test]
+fn kind_in_match() {
+    let label = match AppError::NotFound.kind() {
+        AppErrorKind::NotFound => "not_found",
+        _ => "other",
+    };
+    assert_eq!(label, "not_found");
+}
+
+#[test]
+fn kind_as_map_key() {
+    let errors = [AppError::NotFound, AppError::from(DatabaseError), AppError::NotFound];
+    let mut counts: HashMap<AppErrorKind, usize> = HashMap::new();
+
+    for error in &errors {
+        *counts.entry(error.kind()).or_default() += 1;
+    }
+
+    assert_eq!(counts[&AppErrorKind::NotFound], 2);
+    assert_eq!(counts[&AppErrorKind::Database], 1);
+    assert!(!counts.contains_key(&AppErrorKind::Anyhow));
+}
@@ -0,0 +1,109 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+struct ParseError<'a> {
+    input: &'a str,
+}
+
+#[derive(Debug)]
+struct SpanError<'a, 'b> {
+    input: &'a str,
+    label: &'b str,
+}
+
+#[derive(Debug, ErrorEnum)]
+enum BorrowedError<'a> {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Parse(ParseError<'a>),
+}
+
+#[derive(Debug, PartialEq)]
+struct IoError;
+
+#[derive(Debug, ErrorEnum)]
+enum ReferenceError<'a> {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Io(&'a IoError),
+}
+
+#[derive(Debug)]
+enum LexError<'input> {
+    Anyhow(anyhow::Error),
+    UnexpectedChar(&'input str),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum InputError<'input> {
+    Anyhow(anyhow::Error),
+
+    Lex(LexError<'input>),
+
+    #[without_anyhow]
+    Parse { source: ParseError<'input> },
+}
+
+#[derive(Debug, ErrorEnum)]
+enum TwoLifetimesError<'a, 'b: 'a> {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Parse(ParseError<'a>),
+
+    #[without_anyhow]
+    Span(SpanError<'a, 'b>),
+}
+
+#[test]
+fn borrowed_variant() {
+    let input = String::from("1 + ");
+    let error = BorrowedError::from(ParseError { input: &input });
+    assert!(matches!(error, BorrowedError::Parse(ParseError { input: "1 + " })));
+}
+
+#[test]
+fn borrowed_aggregate() {
+    let error: BorrowedError<'_> = BorrowedError::from(anyhow::Error);
+    assert!(matches!(error, BorrowedError::Anyhow(_)));
+}
+
+#[test]
+fn reference_variant() {
+    let io = IoError;
+    let error = ReferenceError::from(&io);
+    assert!(matches!(error, ReferenceError::Io(&IoError)));
+}
+
+#[test]
+fn two_lifetimes() {
+    let input = String::from("1 + ");
+    let label = String::from("expression");
+
+    let error = TwoLifetimesError::from(SpanError { input: &input, label: &label });
+    assert!(matches!(error, TwoLifetimesError::Span(SpanError { input: "1 + ", label: "expression" })));
+
+    let error = TwoLifetimesError::from(ParseError { input: &input });
+    assert!(matches!(error, TwoLifetimesError::Parse(ParseError { input: "1 + " })));
+}
+
+#[test]
+fn inner_type_shares_enum_lifetime() {
+    let input = String::from("1 $ 2");
+
+    let error = InputError::from(LexError::UnexpectedChar(&input[2..3]));
+    assert!(matches!(error, InputError::Lex(LexError::UnexpectedChar("$"))));
+
+    let error = InputError::from(LexError::Anyhow(anyhow::Error));
+    assert!(matches!(error, InputError::Anyhow(_)));
+
+    let error = InputError::from(ParseError { input: &input });
+    assert!(matches!(error, InputError::Parse { source: ParseError { input: "1 $ 2" } }));
+}
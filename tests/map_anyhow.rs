@@ -0,0 +1,55 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(pub String);
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug)]
+enum CacheError {
+    Anyhow(anyhow::Error),
+    Evicted,
+}
+
+mod redact {
+    pub fn uppercase(error: crate::anyhow::Error) -> crate::anyhow::Error {
+        crate::anyhow::Error(error.0.to_uppercase())
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[map_anyhow(redact::uppercase)]
+    Database(DatabaseError),
+
+    Cache(CacheError),
+}
+
+#[test]
+fn extracted_aggregate_is_mapped() {
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error(String::from("connection lost"))));
+    assert!(matches!(error, AppError::Anyhow(anyhow::Error(message)) if message == "CONNECTION LOST"));
+}
+
+#[test]
+fn other_inner_variants_are_wrapped() {
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+
+    let error = AppError::from(CacheError::Evicted);
+    assert!(matches!(error, AppError::Cache(CacheError::Evicted)));
+}
+
+#[test]
+fn unmarked_variants_are_not_mapped() {
+    let error = AppError::from(CacheError::Anyhow(anyhow::Error(String::from("evicted"))));
+    assert!(matches!(error, AppError::Anyhow(anyhow::Error(message)) if message == "evicted"));
+}
@@ -0,0 +1,43 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+struct DatabaseError;
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(predicates, accessors, predicate_prefix = "variant_is_", accessor_prefix = "variant_as_")]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Database(DatabaseError),
+
+    NotFound,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(predicates, accessors, predicate_prefix = "holds_")]
+enum DefaultAccessorError {
+    Anyhow(anyhow::Error),
+}
+
+#[test]
+fn custom_prefixes() {
+    let error = AppError::from(DatabaseError);
+    assert!(error.variant_is_database());
+    assert!(!AppError::NotFound.variant_is_database());
+    assert!(AppError::NotFound.variant_is_not_found());
+    assert_eq!(error.variant_as_database(), Some(&DatabaseError));
+    assert!(error.variant_as_anyhow().is_none());
+}
+
+#[test]
+fn unset_prefix_keeps_default() {
+    let error = DefaultAccessorError::from(anyhow::Error);
+    assert!(error.holds_anyhow());
+    assert!(error.as_anyhow().is_some());
+}
@@ -0,0 +1,112 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("unexpected")
+        }
+    }
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &super::IoError
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct IoError;
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("io failed")
+    }
+}
+
+impl std::error::Error for IoError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(std_error, predicates, accessors, into_inner, kind)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    Io(IoError),
+
+    #[wrap]
+    #[error("request {id} failed")]
+    Request { id: u32, #[from] source: IoError2 },
+
+    #[skip_from]
+    #[error("timed out after {0}s")]
+    Timeout(u64, String),
+
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+struct IoError2;
+
+impl fmt::Display for IoError2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("request io failed")
+    }
+}
+
+impl std::error::Error for IoError2 {}
+
+#[test]
+fn tuple_variant() {
+    let error = AppError::from(IoError);
+    assert!(error.is_io());
+    assert_eq!(error.as_io(), Some(&IoError));
+    assert_eq!(error.kind(), AppErrorKind::Io);
+    assert_eq!(error.to_string(), "io failed");
+    assert_eq!(error.source().unwrap().to_string(), "io failed");
+    assert_eq!(error.into_io().ok(), Some(IoError));
+}
+
+#[test]
+fn named_variant_with_several_fields() {
+    let error = AppError::from(IoError2);
+    assert!(error.is_request());
+    assert_eq!(error.kind(), AppErrorKind::Request);
+    assert_eq!(error.to_string(), "request 0 failed");
+    assert!(error.source().is_none());
+    assert!(matches!(error, AppError::Request { id: 0, source: IoError2 }));
+}
+
+#[test]
+fn tuple_variant_with_several_fields() {
+    let error = AppError::Timeout(30, String::from("upstream"));
+    assert!(error.is_timeout());
+    assert_eq!(error.kind(), AppErrorKind::Timeout);
+    assert_eq!(error.to_string(), "timed out after 30s");
+    assert!(matches!(error, AppError::Timeout(30, ref upstream) if upstream == "upstream"));
+}
+
+#[test]
+fn unit_variant() {
+    let error = AppError::NotFound;
+    assert!(error.is_not_found());
+    assert!(error.as_io().is_none());
+    assert_eq!(error.kind(), AppErrorKind::NotFound);
+    assert_eq!(error.to_string(), "NotFound");
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn aggregate_variant() {
+    let error = AppError::from(anyhow::Error);
+    assert!(error.is_anyhow());
+    assert_eq!(error.to_string(), "unexpected");
+    assert_eq!(error.source().unwrap().to_string(), "io failed");
+}
@@ -0,0 +1,89 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(pub &'static str);
+}
+
+#[derive(Debug, PartialEq)]
+struct Payload([u8; 256]);
+
+// Every shape of variant next to the aggregate, so that the extraction has to move out of one arm only
+#[derive(Debug)]
+enum StorageError {
+    Anyhow(anyhow::Error),
+    Closed,
+    Corrupted(Payload),
+    Timeout(u64, String),
+    Conflict { key: String, expected: Box<Payload>, actual: Option<Payload> },
+    Nested(Box<StorageError>),
+    Many(Vec<String>),
+    Tuple((u8, String)),
+    Empty {},
+    Unit(()),
+}
+
+// Dropping the inner enum forbids moving out of it, so it is wrapped as a whole
+#[derive(Debug)]
+enum GuardError {
+    #[allow(dead_code)]
+    Anyhow(anyhow::Error),
+    Poisoned,
+}
+
+impl Drop for GuardError {
+    fn drop(&mut self) {}
+}
+
+// The large inner enum is moved into the variant as is
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    Storage(StorageError),
+
+    #[wrap]
+    Guard(GuardError),
+}
+
+#[test]
+fn aggregate_is_moved_out() {
+    let error = AppError::from(StorageError::Anyhow(anyhow::Error("lost connection")));
+    assert!(matches!(error, AppError::Anyhow(anyhow::Error("lost connection"))));
+}
+
+#[test]
+fn other_variants_are_moved_whole() {
+    let error = AppError::from(StorageError::Corrupted(Payload([7; 256])));
+    assert!(matches!(error, AppError::Storage(StorageError::Corrupted(Payload(bytes))) if bytes == [7; 256]));
+
+    let error = AppError::from(StorageError::Timeout(30, String::from("pool")));
+    assert!(matches!(error, AppError::Storage(StorageError::Timeout(30, pool)) if pool == "pool"));
+
+    let error = AppError::from(StorageError::Conflict {
+        key: String::from("id"),
+        expected: Box::new(Payload([0; 256])),
+        actual: None,
+    });
+    assert!(matches!(error, AppError::Storage(StorageError::Conflict { key, expected, actual: None }) if key == "id" && *expected == Payload([0; 256])));
+
+    let error = AppError::from(StorageError::Nested(Box::new(StorageError::Anyhow(anyhow::Error("nested")))));
+    assert!(matches!(error, AppError::Storage(StorageError::Nested(inner)) if matches!(*inner, StorageError::Anyhow(anyhow::Error("nested")))));
+
+    let error = AppError::from(StorageError::Many(vec![String::from("a")]));
+    assert!(matches!(error, AppError::Storage(StorageError::Many(items)) if items == ["a"]));
+
+    let error = AppError::from(StorageError::Tuple((1, String::from("b"))));
+    assert!(matches!(error, AppError::Storage(StorageError::Tuple((1, b))) if b == "b"));
+
+    assert!(matches!(AppError::from(StorageError::Closed), AppError::Storage(StorageError::Closed)));
+    assert!(matches!(AppError::from(StorageError::Empty {}), AppError::Storage(StorageError::Empty {})));
+    assert!(matches!(AppError::from(StorageError::Unit(())), AppError::Storage(StorageError::Unit(()))));
+}
+
+#[test]
+fn drop_type_is_wrapped() {
+    let error = AppError::from(GuardError::Poisoned);
+    assert!(matches!(error, AppError::Guard(GuardError::Poisoned)));
+}
@@ -0,0 +1,61 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct StatusCode(u16);
+
+#[derive(Debug)]
+enum RequestError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug)]
+struct DecodeError;
+
+#[derive(Debug)]
+enum QueryError {
+    Anyhow(anyhow::Error),
+    Syntax,
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+    Http(StatusCode, #[from] RequestError),
+    Query { index: usize, #[from] source: QueryError },
+
+    #[without_anyhow]
+    Decode(#[from] DecodeError, String),
+}
+
+#[test]
+fn marked_second_field() {
+    let error = AppError::from(RequestError::Timeout);
+    assert!(matches!(error, AppError::Http(StatusCode(0), RequestError::Timeout)));
+}
+
+#[test]
+fn marked_second_field_extracts_anyhow() {
+    let error = AppError::from(RequestError::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn marked_named_field_defaults_index() {
+    let error = AppError::from(QueryError::Syntax);
+    assert!(matches!(error, AppError::Query { index: 0, source: QueryError::Syntax }));
+
+    let error = AppError::from(QueryError::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn marked_first_field_without_anyhow() {
+    let error = AppError::from(DecodeError);
+    assert!(matches!(error, AppError::Decode(DecodeError, message) if message.is_empty()));
+}
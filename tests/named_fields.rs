@@ -0,0 +1,42 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    AnyhowError(anyhow::Error),
+    ConnectionLost,
+}
+
+#[derive(Debug, PartialEq)]
+struct ConfigError;
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    AnyhowError(anyhow::Error),
+    Database { source: DatabaseError },
+
+    #[without_anyhow]
+    Config { source: ConfigError },
+}
+
+#[test]
+fn named_field_extracts_anyhow() {
+    let error = AppError::from(DatabaseError::AnyhowError(anyhow::Error));
+    assert!(matches!(error, AppError::AnyhowError(_)));
+}
+
+#[test]
+fn named_field_wraps_other_variants() {
+    let error = AppError::from(DatabaseError::ConnectionLost);
+    assert!(matches!(error, AppError::Database { source: DatabaseError::ConnectionLost }));
+}
+
+#[test]
+fn named_field_without_anyhow() {
+    let error = AppError::from(ConfigError);
+    assert!(matches!(error, AppError::Config { source: ConfigError }));
+}
@@ -0,0 +1,35 @@
+use error_conversion_macro::ErrorEnum;
+
+#[derive(Debug)]
+struct IoError;
+
+#[derive(Debug)]
+struct ParseError;
+
+#[derive(Debug)]
+struct ConfigError;
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(no_aggregate)]
+enum AppError {
+    Io(IoError),
+    Parse(ParseError),
+
+    #[without_anyhow]
+    Config { source: ConfigError },
+}
+
+#[test]
+fn wraps_tuple_variants() {
+    let error = AppError::from(IoError);
+    assert!(matches!(error, AppError::Io(IoError)));
+
+    let error = AppError::from(ParseError);
+    assert!(matches!(error, AppError::Parse(ParseError)));
+}
+
+#[test]
+fn wraps_without_anyhow_variants() {
+    let error = AppError::from(ConfigError);
+    assert!(matches!(error, AppError::Config { source: ConfigError }));
+}
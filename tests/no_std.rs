@@ -0,0 +1,79 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use core::error::Error as _;
+
+use error_conversion_macro::ErrorEnum;
+
+mod report {
+    use core::fmt;
+
+    #[derive(Debug)]
+    pub struct Report;
+
+    impl fmt::Display for Report {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("report")
+        }
+    }
+
+    impl core::error::Error for Report {}
+
+    impl AsRef<dyn core::error::Error> for Report {
+        fn as_ref(&self) -> &(dyn core::error::Error + 'static) {
+            self
+        }
+    }
+}
+
+#[derive(Debug)]
+enum SensorError {
+    Report(report::Report),
+    Disconnected,
+}
+
+#[derive(Debug)]
+struct BusError;
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(no_std, std_error, aggregate = "report::Report")]
+enum DeviceError {
+    #[error("device failed")]
+    Report(report::Report),
+
+    #[without_anyhow]
+    #[error("sensor failed")]
+    Sensor(SensorError),
+
+    #[boxed]
+    #[without_anyhow]
+    #[error("bus failed")]
+    Bus(Box<BusError>),
+}
+
+#[test]
+fn wraps_variants() {
+    let error = DeviceError::from(SensorError::Disconnected);
+    assert!(matches!(error, DeviceError::Sensor(SensorError::Disconnected)));
+
+    let error = DeviceError::from(BusError);
+    assert!(matches!(error, DeviceError::Bus(inner) if matches!(*inner, BusError)));
+}
+
+#[test]
+fn converts_aggregate() {
+    let error = DeviceError::from(report::Report);
+    assert!(matches!(error, DeviceError::Report(_)));
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn implements_display() {
+    use core::fmt::Write;
+
+    let mut message = alloc::string::String::new();
+    write!(message, "{}", DeviceError::from(SensorError::Report(report::Report))).unwrap();
+    assert_eq!(message, "sensor failed");
+}
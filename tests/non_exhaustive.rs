@@ -0,0 +1,108 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(Box<dyn std::error::Error + Send + Sync>);
+
+    #[derive(Debug)]
+    struct Message(String);
+
+    impl fmt::Display for Message {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl std::error::Error for Message {}
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(Box::new(error))
+        }
+
+        pub fn msg<M: fmt::Display>(message: M) -> Self {
+            Error(Box::new(Message(message.to_string())))
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &*self.0
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid syntax")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[non_exhaustive]
+#[error_enum(std_error, predicates, accessors, into_inner, kind)]
+enum AppError {
+    #[error("unexpected: {0}")]
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    #[error("parse failed")]
+    Parse(ParseError),
+
+    #[error("not found")]
+    NotFound,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[non_exhaustive]
+#[error_enum(into_aggregate)]
+enum ServiceError {
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    Parse(ParseError),
+
+    NotFound,
+}
+
+#[test]
+fn generated_helpers_work() {
+    let error = AppError::from(ParseError);
+    assert!(error.is_parse());
+    assert!(error.as_parse().is_some());
+    assert_eq!(error.kind(), AppErrorKind::Parse);
+    assert_eq!(error.to_string(), "parse failed");
+    assert_eq!(error.source().unwrap().to_string(), "invalid syntax");
+    assert!(error.into_parse().is_ok());
+}
+
+#[test]
+fn unit_variant() {
+    assert_eq!(AppError::NotFound.kind(), AppErrorKind::NotFound);
+    assert!(AppError::NotFound.source().is_none());
+}
+
+#[test]
+fn into_aggregate() {
+    let aggregate: anyhow::Error = ServiceError::from(ParseError).into();
+    assert_eq!(aggregate.to_string(), "invalid syntax");
+
+    let aggregate: anyhow::Error = ServiceError::NotFound.into();
+    assert_eq!(aggregate.to_string(), "NotFound");
+}
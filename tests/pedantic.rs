@@ -0,0 +1,62 @@
+#![deny(clippy::pedantic)]
+
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &super::Message
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Message;
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("message")
+    }
+}
+
+impl std::error::Error for Message {}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("database error")
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(std_error, predicates, accessors, into_inner)]
+enum AppError {
+    #[error("unexpected")]
+    Anyhow(anyhow::Error),
+    Database(DatabaseError),
+    #[without_anyhow]
+    Format { source: fmt::Error },
+    NotFound,
+}
+
+#[test]
+fn derives_under_pedantic() {
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(error.is_database());
+    assert!(error.as_format().is_none());
+    assert!(AppError::from(DatabaseError::Anyhow(anyhow::Error)).is_anyhow());
+    assert!(AppError::from(fmt::Error).into_format().is_ok());
+    assert_eq!(AppError::NotFound.to_string(), "NotFound");
+}
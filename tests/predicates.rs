@@ -0,0 +1,42 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+struct DatabaseError;
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+#[error_enum(predicates)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Database { source: DatabaseError },
+
+    NotFound,
+
+    #[skip_from]
+    HTTPTimeout(u64, String),
+}
+
+#[test]
+fn predicates_on_each_shape() {
+    let error = AppError::from(anyhow::Error);
+    assert!(error.is_anyhow());
+    assert!(!error.is_not_found());
+
+    let error = AppError::from(DatabaseError);
+    assert!(error.is_database());
+    assert!(!error.is_anyhow());
+
+    assert!(AppError::NotFound.is_not_found());
+    assert!(!AppError::NotFound.is_database());
+
+    let error = AppError::HTTPTimeout(30, String::from("upstream"));
+    assert!(error.is_http_timeout());
+    assert!(!error.is_not_found());
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(Box<dyn std::error::Error + Send + Sync>);
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(Box::new(error))
+        }
+
+        pub fn msg<M: fmt::Display>(message: M) -> Self {
+            Error(Box::from(message.to_string()))
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &*self.0
+        }
+    }
+}
+
+// A project prelude re-exporting the aggregate, glob-imported so that fields just say `Error`
+mod prelude {
+    pub use crate::anyhow::Error;
+}
+
+use prelude::*;
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(Error),
+    Timeout,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseError::Anyhow(error) => fmt::Display::fmt(error, f),
+            DatabaseError::Timeout => f.write_str("database timed out"),
+        }
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "Error", into_anyhow_method)]
+enum AppError {
+    Anyhow(Error),
+
+    Database(DatabaseError),
+
+    #[without_anyhow]
+    Message(String),
+}
+
+#[test]
+fn bare_error_is_the_aggregate() {
+    let error = AppError::from(Error::msg("unexpected"));
+    assert!(matches!(error, AppError::Anyhow(inner) if inner.to_string() == "unexpected"));
+}
+
+#[test]
+fn bare_error_is_extracted() {
+    let error = AppError::from(DatabaseError::Anyhow(Error::msg("pool exhausted")));
+    assert!(matches!(error, AppError::Anyhow(inner) if inner.to_string() == "pool exhausted"));
+
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn converts_back_into_bare_error() {
+    let error: Error = AppError::from(DatabaseError::Timeout).into_anyhow();
+    assert_eq!(error.to_string(), "database timed out");
+
+    let error: Error = AppError::from(String::from("invalid")).into_anyhow();
+    assert_eq!(error.to_string(), "invalid");
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+// Like anyhow, an error with context downcasts into both its context and the error it wraps
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(Vec<Box<dyn std::error::Error + Send + Sync>>);
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(vec![Box::new(error)])
+        }
+
+        pub fn context<C: std::error::Error + Send + Sync + 'static>(mut self, context: C) -> Self {
+            self.0.insert(0, Box::new(context));
+            self
+        }
+
+        pub fn downcast<E: std::error::Error + Send + Sync + 'static>(mut self) -> Result<E, Self> {
+            match self.0.iter().position(|layer| layer.is::<E>()) {
+                Some(index) => Ok(*self.0.remove(index).downcast().unwrap()),
+                None => Err(self),
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct QueryFailed;
+
+impl fmt::Display for QueryFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("query failed")
+    }
+}
+
+impl std::error::Error for QueryFailed {}
+
+#[derive(Debug, PartialEq)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timeout")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(downcast_from_anyhow)]
+enum DeclarationOrder {
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    Query(QueryFailed),
+
+    #[wrap]
+    Timeout(Timeout),
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(downcast_from_anyhow)]
+enum Prioritized {
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    Query(QueryFailed),
+
+    #[wrap]
+    #[priority(-1)]
+    Timeout(Timeout),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum PrioritizedTryFrom {
+    Anyhow(anyhow::Error),
+
+    #[try_from]
+    #[priority(2)]
+    Query(QueryFailed),
+
+    #[try_from]
+    #[priority(1)]
+    Timeout(Timeout),
+}
+
+fn timeout_while_querying() -> anyhow::Error {
+    anyhow::Error::new(Timeout).context(QueryFailed)
+}
+
+#[test]
+fn declaration_order_without_priority() {
+    let error = DeclarationOrder::from(timeout_while_querying());
+    assert!(matches!(error, DeclarationOrder::Query(QueryFailed)));
+}
+
+#[test]
+fn lower_priority_is_tried_first() {
+    let error = Prioritized::from(timeout_while_querying());
+    assert!(matches!(error, Prioritized::Timeout(Timeout)));
+
+    let error = Prioritized::from(anyhow::Error::new(QueryFailed));
+    assert!(matches!(error, Prioritized::Query(QueryFailed)));
+}
+
+#[test]
+fn priority_orders_try_from() {
+    let error = PrioritizedTryFrom::try_from(timeout_while_querying());
+    assert!(matches!(error, Ok(PrioritizedTryFrom::Timeout(Timeout))));
+}
+
+#[derive(Debug)]
+struct Unrelated;
+
+impl fmt::Display for Unrelated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unrelated")
+    }
+}
+
+impl std::error::Error for Unrelated {}
+
+#[test]
+fn unmatched_stays_aggregate() {
+    let error = match DeclarationOrder::from(anyhow::Error::new(Unrelated)) {
+        DeclarationOrder::Anyhow(error) => error,
+        _ => panic!("expected the aggregate variant"),
+    };
+    let error = match Prioritized::from(error) {
+        Prioritized::Anyhow(error) => error,
+        _ => panic!("expected the aggregate variant"),
+    };
+    let error = match PrioritizedTryFrom::try_from(error) {
+        Err(PrioritizedTryFrom::Anyhow(error)) => error,
+        _ => panic!("expected the aggregate variant"),
+    };
+    assert!(error.downcast::<Unrelated>().is_ok());
+}
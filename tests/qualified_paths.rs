@@ -0,0 +1,85 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+mod db {
+    #[derive(Debug)]
+    pub enum DbError {
+        Anyhow(crate::anyhow::Error),
+        Closed,
+    }
+
+    pub mod pool {
+        #[derive(Debug)]
+        pub enum PoolError<T> {
+            Anyhow(crate::anyhow::Error),
+            Exhausted(T),
+            Busy,
+        }
+    }
+}
+
+macro_rules! error_enum {
+    ($name:ident, $variant:ident, $t:ty) => {
+        #[derive(Debug, ErrorEnum)]
+        enum $name {
+            Anyhow(anyhow::Error),
+            $variant($t),
+        }
+    };
+}
+
+mod app {
+    use super::anyhow;
+    use error_conversion_macro::ErrorEnum;
+
+    #[derive(Debug, ErrorEnum)]
+    pub enum AppError {
+        Anyhow(anyhow::Error),
+        Database(super::db::DbError),
+
+        #[flatten(Busy)]
+        Pool(crate::db::pool::PoolError<u32>),
+
+        Busy,
+    }
+}
+
+// The variant name comes from outside the macro, with other hygiene than the derive
+error_enum!(MacroError, Pool, db::pool::PoolError<String>);
+
+#[test]
+fn qualified_inner_is_wrapped() {
+    let error = app::AppError::from(db::DbError::Closed);
+    assert!(matches!(error, app::AppError::Database(db::DbError::Closed)));
+}
+
+#[test]
+fn qualified_inner_extracts_anyhow() {
+    let error = app::AppError::from(db::DbError::Anyhow(anyhow::Error));
+    assert!(matches!(error, app::AppError::Anyhow(_)));
+}
+
+#[test]
+fn qualified_generic_inner() {
+    let error = app::AppError::from(db::pool::PoolError::Exhausted(4));
+    assert!(matches!(error, app::AppError::Pool(db::pool::PoolError::Exhausted(4))));
+}
+
+#[test]
+fn qualified_inner_is_flattened() {
+    let error = app::AppError::from(db::pool::PoolError::<u32>::Busy);
+    assert!(matches!(error, app::AppError::Busy));
+}
+
+#[test]
+fn macro_inner_type() {
+    let error = MacroError::from(db::pool::PoolError::Exhausted(String::from("conn")));
+    assert!(matches!(error, MacroError::Pool(db::pool::PoolError::Exhausted(key)) if key == "conn"));
+
+    let error = MacroError::from(db::pool::PoolError::<String>::Anyhow(anyhow::Error));
+    assert!(matches!(error, MacroError::Anyhow(_)));
+}
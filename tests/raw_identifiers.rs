@@ -0,0 +1,52 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+mod r#async {
+    use error_conversion_macro::ErrorEnum;
+
+    use super::anyhow;
+
+    #[derive(Debug, ErrorEnum)]
+    pub enum r#Error {
+        r#Anyhow(r#anyhow::Error),
+        #[without_anyhow]
+        r#Type(std::fmt::Error),
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+#[error_enum(predicates)]
+enum AppError {
+    #[error("unexpected")]
+    Anyhow(anyhow::Error),
+    #[error("async failure")]
+    r#Async(r#async::r#Error),
+    r#Loop,
+}
+
+#[test]
+fn raw_variant_converts() {
+    let error = AppError::from(r#async::Error::Type(std::fmt::Error));
+    assert!(matches!(error, AppError::Async(r#async::Error::Type(_))));
+}
+
+#[test]
+fn raw_aggregate_path_is_extracted() {
+    let error = AppError::from(r#async::Error::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
+
+#[test]
+fn raw_unit_variant_displays_without_prefix() {
+    assert_eq!(AppError::Loop.to_string(), "Loop");
+}
+
+#[test]
+fn raw_variant_predicate() {
+    assert!(AppError::Loop.is_loop());
+}
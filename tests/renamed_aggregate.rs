@@ -0,0 +1,36 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+use anyhow as ah;
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(ah::Error),
+    Timeout,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "ah::Error")]
+enum AppError {
+    Anyhow(ah::Error),
+    Database(DatabaseError),
+}
+
+#[test]
+fn detects_renamed_aggregate() {
+    let error = AppError::from(DatabaseError::Anyhow(ah::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn converts_renamed_aggregate() {
+    let error = AppError::from(ah::Error);
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
@@ -0,0 +1,83 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+struct Timeout;
+
+mod service {
+    use error_conversion_macro::ErrorEnum;
+
+    use super::{anyhow, Timeout};
+
+    #[derive(Debug, ErrorEnum)]
+    #[error_enum(result_alias = "ServiceResult")]
+    pub enum ServiceError {
+        Anyhow(anyhow::Error),
+
+        #[without_anyhow]
+        Timeout(Timeout),
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(result_alias = "Result")]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Timeout(Timeout),
+}
+
+fn connect(fail: bool) -> Result<u16> {
+    match fail {
+        true => Err(Timeout)?,
+        false => Ok(5432),
+    }
+}
+
+#[test]
+fn alias_takes_the_ok_type() {
+    assert_eq!(connect(false).unwrap(), 5432);
+    assert!(matches!(connect(true), Err(AppError::Timeout(Timeout))));
+}
+
+#[test]
+fn alias_has_the_enum_visibility() {
+    let result: service::ServiceResult<()> = Err(anyhow::Error.into());
+    assert!(matches!(result, Err(service::ServiceError::Anyhow(_))));
+}
+
+#[test]
+fn std_result_is_still_available() {
+    let result: std::result::Result<(), AppError> = Err(AppError::from(anyhow::Error));
+    assert!(matches!(result, Err(AppError::Anyhow(_))));
+}
+
+// Generated code spelling out `Result` keeps working next to the alias
+mod storage {
+    use error_conversion_macro::ErrorEnum;
+
+    use super::{anyhow, Timeout};
+
+    #[derive(Debug, ErrorEnum)]
+    #[error_enum(result_alias = "Result", into_inner)]
+    pub enum StorageError {
+        Anyhow(anyhow::Error),
+
+        #[without_anyhow]
+        Timeout(Timeout),
+    }
+}
+
+#[test]
+fn generated_methods_ignore_the_alias() {
+    let error = storage::StorageError::from(Timeout);
+    assert!(matches!(error.into_timeout(), Ok(Timeout)));
+
+    let result: storage::Result<()> = Err(anyhow::Error.into());
+    assert!(matches!(result.map_err(storage::StorageError::into_anyhow), Err(Ok(anyhow::Error))));
+}
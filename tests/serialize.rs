@@ -0,0 +1,167 @@
+#![cfg(feature = "serde")]
+
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+/// Stand-in for the parts of serde the generated impl uses.
+mod serde {
+    use std::fmt;
+
+    pub trait Serialize {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    }
+
+    pub trait Serializer: Sized {
+        type Ok;
+        type Error;
+        type SerializeStruct: ser::SerializeStruct<Ok = Self::Ok, Error = Self::Error>;
+
+        fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error>;
+        fn collect_str<T: fmt::Display + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>;
+    }
+
+    pub mod ser {
+        pub trait SerializeStruct {
+            type Ok;
+            type Error;
+
+            fn serialize_field<T: super::Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>;
+            fn end(self) -> Result<Self::Ok, Self::Error>;
+        }
+    }
+
+    impl Serialize for str {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl Serialize for fmt::Arguments<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+/// Serializes into JSON, with strings escaped just enough for the tests.
+struct Json<'a>(&'a mut String);
+
+struct JsonStruct<'a> {
+    output: &'a mut String,
+    first: bool,
+}
+
+impl<'a> serde::Serializer for Json<'a> {
+    type Ok = ();
+    type Error = fmt::Error;
+    type SerializeStruct = JsonStruct<'a>;
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<JsonStruct<'a>, fmt::Error> {
+        self.0.push('{');
+        Ok(JsonStruct { output: self.0, first: true })
+    }
+
+    fn collect_str<T: fmt::Display + ?Sized>(self, value: &T) -> Result<(), fmt::Error> {
+        let value = value.to_string().replace('\\', "\\\\").replace('"', "\\\"");
+        self.0.push_str(&format!("\"{}\"", value));
+        Ok(())
+    }
+}
+
+impl serde::ser::SerializeStruct for JsonStruct<'_> {
+    type Ok = ();
+    type Error = fmt::Error;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), fmt::Error> {
+        if !self.first {
+            self.output.push(',');
+        }
+
+        self.first = false;
+        self.output.push_str(&format!("\"{}\":", key));
+        value.serialize(Json(self.output))
+    }
+
+    fn end(self) -> Result<(), fmt::Error> {
+        self.output.push('}');
+        Ok(())
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    let mut output = String::new();
+    value.serialize(Json(&mut output)).unwrap();
+    output
+}
+
+#[derive(Debug)]
+struct Secret(&'static str);
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(serialize)]
+enum AppError {
+    #[error("unexpected error")]
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    #[error("invalid credentials")]
+    Credentials(Secret),
+
+    #[error("not found")]
+    NotFound,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(serialize)]
+enum DebugError {
+    Anyhow(anyhow::Error),
+
+    NotFound,
+}
+
+#[test]
+fn serializes_kind_and_message() {
+    assert_eq!(to_json(&AppError::NotFound), r#"{"kind":"NotFound","message":"not found"}"#);
+    assert_eq!(to_json(&AppError::from(anyhow::Error)), r#"{"kind":"Anyhow","message":"unexpected error"}"#);
+}
+
+#[test]
+fn payload_is_not_serialized() {
+    let error = AppError::from(Secret("hunter2"));
+    assert!(matches!(error, AppError::Credentials(Secret(secret)) if !to_json(&error).contains(secret)));
+    assert_eq!(to_json(&error), r#"{"kind":"Credentials","message":"invalid credentials"}"#);
+}
+
+#[test]
+fn debug_without_display() {
+    assert_eq!(to_json(&DebugError::NotFound), r#"{"kind":"NotFound","message":"NotFound"}"#);
+    assert!(matches!(DebugError::from(anyhow::Error), DebugError::Anyhow(_)));
+}
+
+mod result_alias {
+    use super::*;
+
+    #[derive(Debug, ErrorEnum)]
+    #[error_enum(serialize, result_alias = "Result")]
+    enum AliasedError {
+        Anyhow(anyhow::Error),
+
+        NotFound,
+    }
+
+    fn find() -> Result<()> {
+        Err(AliasedError::NotFound)
+    }
+
+    #[test]
+    fn serializes_alongside_result_alias() {
+        assert_eq!(to_json(&find().unwrap_err()), r#"{"kind":"NotFound","message":"NotFound"}"#);
+        assert!(matches!(AliasedError::from(anyhow::Error), AliasedError::Anyhow(_)));
+    }
+}
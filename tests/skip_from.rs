@@ -0,0 +1,102 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+struct IoError;
+
+#[derive(Debug)]
+struct ParseError;
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[skip_from]
+    Io(IoError),
+
+    #[skip_from]
+    #[without_anyhow]
+    Parse(ParseError),
+}
+
+// Would conflict with a generated impl if `skip_from` were ignored
+impl From<IoError> for AppError {
+    fn from(value: IoError) -> Self {
+        AppError::Io(value)
+    }
+}
+
+impl From<ParseError> for AppError {
+    fn from(value: ParseError) -> Self {
+        AppError::Parse(value)
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+enum ManualAggregateError {
+    #[skip_from]
+    Anyhow(anyhow::Error),
+}
+
+impl From<anyhow::Error> for ManualAggregateError {
+    fn from(value: anyhow::Error) -> Self {
+        ManualAggregateError::Anyhow(value)
+    }
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(no_anyhow_from)]
+enum ServiceError {
+    Anyhow(anyhow::Error),
+    Database(DatabaseError),
+    Manual,
+}
+
+impl From<anyhow::Error> for ServiceError {
+    fn from(_: anyhow::Error) -> Self {
+        ServiceError::Manual
+    }
+}
+
+#[test]
+fn skipped_variant_uses_manual_impl() {
+    let error = AppError::from(IoError);
+    assert!(matches!(error, AppError::Io(IoError)));
+}
+
+#[test]
+fn skip_wins_over_without_anyhow() {
+    let error = AppError::from(ParseError);
+    assert!(matches!(error, AppError::Parse(ParseError)));
+}
+
+#[test]
+fn skipped_aggregate_variant_uses_manual_impl() {
+    let error = ManualAggregateError::from(anyhow::Error);
+    assert!(matches!(error, ManualAggregateError::Anyhow(_)));
+}
+
+#[test]
+fn no_anyhow_from_uses_manual_impl() {
+    let error = ServiceError::from(anyhow::Error);
+    assert!(matches!(error, ServiceError::Manual));
+}
+
+#[test]
+fn no_anyhow_from_still_extracts() {
+    let error = ServiceError::from(DatabaseError::Anyhow(anyhow::Error));
+    assert!(matches!(error, ServiceError::Anyhow(_)));
+
+    let error = ServiceError::from(DatabaseError::Timeout);
+    assert!(matches!(error, ServiceError::Database(DatabaseError::Timeout)));
+}
@@ -0,0 +1,56 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    /// Only offers `new`, so conversions that format the error instead wouldn't compile.
+    #[derive(Debug)]
+    pub struct Error(Box<dyn std::error::Error + Send + Sync>);
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(Box::new(error))
+        }
+    }
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &*self.0
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ConfigError {
+    source: std::num::ParseIntError,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid config")
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(std_error)]
+enum AppError {
+    #[from_types(ConfigError)]
+    #[error("unexpected")]
+    Anyhow(anyhow::Error),
+}
+
+#[test]
+fn wrapped_error_is_the_source() {
+    let error = AppError::from(ConfigError { source: "x".parse::<u8>().unwrap_err() });
+    let source = error.source().unwrap();
+
+    assert_eq!(source.to_string(), "invalid config");
+    assert_eq!(source.source().unwrap().to_string(), "invalid digit found in string");
+}
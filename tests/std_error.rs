@@ -0,0 +1,96 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(pub Box<dyn std::error::Error + Send + Sync>);
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &*self.0
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Message(&'static str);
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for Message {}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseError::Anyhow(e) => fmt::Display::fmt(e, f),
+            DatabaseError::Timeout => f.write_str("database timed out"),
+        }
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(std_error)]
+enum AppError {
+    Anyhow(anyhow::Error),
+    Database(DatabaseError),
+
+    #[without_anyhow]
+    Custom(String),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Custom(message) => f.write_str(message),
+            _ => f.write_str("application error"),
+        }
+    }
+}
+
+#[test]
+fn source_of_wrapped_error() {
+    let error = AppError::from(DatabaseError::Timeout);
+    assert_eq!(error.source().unwrap().to_string(), "database timed out");
+}
+
+#[test]
+fn source_of_aggregate() {
+    let error = AppError::from(anyhow::Error(Box::new(Message("connection refused"))));
+    assert_eq!(error.source().unwrap().to_string(), "connection refused");
+}
+
+#[test]
+fn source_of_extracted_aggregate() {
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error(Box::new(Message("pool exhausted")))));
+    assert!(matches!(error, AppError::Anyhow(_)));
+    assert_eq!(error.source().unwrap().to_string(), "pool exhausted");
+}
+
+#[test]
+fn without_anyhow_has_no_source() {
+    let error = AppError::from(String::from("custom"));
+    assert_eq!(error.to_string(), "custom");
+    assert!(error.source().is_none());
+}
@@ -0,0 +1,35 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, PartialEq)]
+struct DatabaseError;
+
+#[derive(Debug, PartialEq)]
+struct ParseError;
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(strict)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Database(DatabaseError),
+
+    #[without_anyhow]
+    Parse { #[from] source: ParseError, line: usize },
+
+    #[skip_from]
+    NotFound,
+}
+
+#[test]
+fn every_variant_converts() {
+    assert!(matches!(AppError::from(anyhow::Error), AppError::Anyhow(_)));
+    assert!(matches!(AppError::from(DatabaseError), AppError::Database(DatabaseError)));
+    assert!(matches!(AppError::from(ParseError), AppError::Parse { source: ParseError, line: 0 }));
+    assert!(matches!(AppError::NotFound, AppError::NotFound));
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "tracing")]
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+/// Subscriber stand-in recording the events of the current thread.
+mod tracing {
+    use std::cell::RefCell;
+
+    thread_local! {
+        pub static EVENTS: RefCell<Vec<(&'static str, &'static str, &'static str)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    macro_rules! debug {
+        (target: $target:expr, from = $from:expr, $message:expr) => {
+            $crate::tracing::EVENTS.with(|events| events.borrow_mut().push(($target, $from, $message)))
+        };
+    }
+
+    pub(crate) use debug;
+
+    pub fn take_events() -> Vec<(&'static str, &'static str, &'static str)> {
+        EVENTS.with(|events| events.take())
+    }
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ErrorEnum)]
+#[error_enum(trace)]
+enum AppError {
+    #[from_types(std::fmt::Error)]
+    Anyhow(anyhow::Error),
+
+    Database(DatabaseError),
+
+    #[without_anyhow]
+    Parse(std::num::ParseIntError),
+}
+
+impl anyhow::Error {
+    fn new(_: std::fmt::Error) -> Self {
+        anyhow::Error
+    }
+}
+
+#[test]
+fn variant_conversion_is_traced() {
+    let _ = AppError::from(DatabaseError::Timeout);
+    let _ = AppError::from("x".parse::<u8>().unwrap_err());
+
+    let events = tracing::take_events();
+    let from: Vec<_> = events.iter().map(|(_, from, _)| *from).collect();
+
+    assert_eq!(from, [std::any::type_name::<DatabaseError>(), std::any::type_name::<std::num::ParseIntError>()]);
+    assert!(events.iter().all(|(target, _, message)| *target == "error_conversion" && *message == "converted"));
+}
+
+#[test]
+fn aggregate_conversions_are_traced() {
+    let _ = AppError::from(anyhow::Error);
+    let _ = AppError::from(std::fmt::Error);
+    let _ = AppError::from(DatabaseError::Anyhow(anyhow::Error));
+
+    let from: Vec<_> = tracing::take_events().into_iter().map(|(_, from, _)| from).collect();
+
+    assert_eq!(from, [
+        std::any::type_name::<anyhow::Error>(),
+        std::any::type_name::<std::fmt::Error>(),
+        std::any::type_name::<DatabaseError>(),
+    ]);
+}
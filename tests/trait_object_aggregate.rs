@@ -0,0 +1,90 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, PartialEq)]
+struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid syntax")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Timeout,
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("database timed out")
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "Box<dyn std::error::Error + Send + Sync>", std_error)]
+enum AppError {
+    #[error("unexpected")]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("database")]
+    Database(DatabaseError),
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "Box<dyn std::error::Error + Send + Sync>", into_aggregate)]
+enum ServiceError {
+    Other(Box<dyn std::error::Error + Send + Sync>),
+
+    #[try_from]
+    Parse(ParseError),
+
+    Database(DatabaseError),
+
+    #[without_anyhow]
+    Status(u16),
+}
+
+#[test]
+fn trait_object_converts_into_aggregate() {
+    let error = AppError::from(BoxError::from("unexpected"));
+    assert!(matches!(error, AppError::Other(inner) if inner.to_string() == "unexpected"));
+}
+
+#[test]
+fn concrete_variants_are_wrapped() {
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn trait_object_is_the_source() {
+    let error = AppError::from(BoxError::from("unexpected"));
+    assert_eq!(error.source().unwrap().to_string(), "unexpected");
+}
+
+#[test]
+fn trait_object_is_downcast() {
+    let error = ServiceError::try_from(BoxError::from(ParseError)).unwrap();
+    assert!(matches!(error, ServiceError::Parse(ParseError)));
+
+    let error = ServiceError::try_from(BoxError::from("unexpected")).unwrap_err();
+    assert!(matches!(error, ServiceError::Other(_)));
+}
+
+#[test]
+fn converts_back_into_trait_object() {
+    let aggregate: BoxError = ServiceError::from(DatabaseError::Timeout).into();
+    assert_eq!(aggregate.to_string(), "database timed out");
+
+    let aggregate: BoxError = ServiceError::from(404).into();
+    assert_eq!(aggregate.to_string(), "404");
+}
@@ -0,0 +1,85 @@
+use std::error::Error as _;
+use std::fmt;
+use std::thread;
+
+use error_conversion_macro::ErrorEnum;
+
+type SendError = Box<dyn std::error::Error + Send + Sync>;
+type LocalError = Box<dyn std::error::Error>;
+
+#[derive(Debug)]
+struct DatabaseError;
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("database timed out")
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+// The bounds are written in another order than in the configured aggregate
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "Box<dyn std::error::Error + Send + Sync>", into_aggregate)]
+enum SendAppError {
+    Other(Box<dyn std::error::Error + Sync + Send>),
+
+    #[wrap]
+    Database(DatabaseError),
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(aggregate = "Box<dyn std::error::Error>", std_error)]
+enum LocalAppError {
+    #[error("unexpected")]
+    Other(Box<dyn std::error::Error>),
+
+    #[error("database")]
+    #[wrap]
+    Database(DatabaseError),
+}
+
+fn fails_send() -> Result<(), SendError> {
+    Err(SendError::from("connection reset"))
+}
+
+fn fails_local() -> Result<(), LocalError> {
+    Err(LocalError::from("connection reset"))
+}
+
+fn question_mark_send() -> Result<(), SendAppError> {
+    fails_send()?;
+    Ok(())
+}
+
+fn question_mark_local() -> Result<(), LocalAppError> {
+    fails_local()?;
+    Ok(())
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync_aggregate_stays_send() {
+    assert_send_sync::<SendAppError>();
+
+    let error = thread::spawn(|| question_mark_send().unwrap_err()).join().unwrap();
+    assert!(matches!(error, SendAppError::Other(inner) if inner.to_string() == "connection reset"));
+}
+
+#[test]
+fn send_sync_aggregate_converts_back() {
+    let aggregate: SendError = SendAppError::from(DatabaseError).into();
+    let aggregate = thread::spawn(move || aggregate.to_string()).join().unwrap();
+    assert_eq!(aggregate, "database timed out");
+}
+
+#[test]
+fn bare_trait_object_aggregate() {
+    let error = question_mark_local().unwrap_err();
+    assert!(matches!(&error, LocalAppError::Other(inner) if inner.to_string() == "connection reset"));
+    assert_eq!(error.source().unwrap().to_string(), "connection reset");
+
+    let error = LocalAppError::from(DatabaseError);
+    assert!(matches!(error, LocalAppError::Database(DatabaseError)));
+}
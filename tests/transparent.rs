@@ -0,0 +1,86 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("unexpected")
+        }
+    }
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &super::IoError
+        }
+    }
+}
+
+#[derive(Debug)]
+struct IoError;
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("connection reset")
+    }
+}
+
+impl std::error::Error for IoError {}
+
+#[derive(Debug)]
+struct ConfigError {
+    source: IoError,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("could not read config")
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(std_error)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    #[transparent]
+    Config(ConfigError),
+
+    #[wrap]
+    Io(IoError),
+}
+
+#[test]
+fn transparent_display_is_the_inner_display() {
+    let inner = ConfigError { source: IoError };
+    let expected = inner.to_string();
+
+    let error = AppError::from(inner);
+    assert_eq!(error.to_string(), expected);
+}
+
+#[test]
+fn transparent_source_is_the_inner_source() {
+    let error = AppError::from(ConfigError { source: IoError });
+    assert_eq!(error.source().unwrap().to_string(), "connection reset");
+}
+
+#[test]
+fn other_variants_keep_their_source() {
+    let error = AppError::from(IoError);
+    assert_eq!(error.to_string(), "connection reset");
+    assert!(error.source().unwrap().source().is_none());
+}
@@ -0,0 +1,64 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error(Box<dyn std::error::Error + Send + Sync>);
+
+    impl Error {
+        pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+            Error(Box::new(error))
+        }
+
+        pub fn downcast<E: std::error::Error + Send + Sync + 'static>(self) -> Result<E, Self> {
+            self.0.downcast().map(|error| *error).map_err(Error)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct NotFound;
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not found")
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timeout")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[try_from]
+    NotFound(NotFound),
+}
+
+#[test]
+fn successful_downcast() {
+    let error = AppError::try_from(anyhow::Error::new(NotFound));
+    assert!(matches!(error, Ok(AppError::NotFound(NotFound))));
+}
+
+#[test]
+fn failing_downcast() {
+    let error = AppError::try_from(anyhow::Error::new(Timeout));
+    let error = match error {
+        Err(AppError::Anyhow(error)) => error,
+        _ => panic!("expected the aggregate variant"),
+    };
+    assert!(error.downcast::<Timeout>().is_ok());
+}
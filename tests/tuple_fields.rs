@@ -0,0 +1,33 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    Rpc((u32, String)),
+
+    Request { source: (u16, &'static str) },
+}
+
+#[test]
+fn tuple_is_wrapped() {
+    let error = AppError::from((404, String::from("missing")));
+    assert!(matches!(error, AppError::Rpc((404, ref message)) if message == "missing"));
+}
+
+#[test]
+fn named_tuple_is_wrapped() {
+    let error = AppError::from((503, "unavailable"));
+    assert!(matches!(error, AppError::Request { source: (503, "unavailable") }));
+}
+
+#[test]
+fn aggregate_still_converts() {
+    let error = AppError::from(anyhow::Error);
+    assert!(matches!(error, AppError::Anyhow(_)));
+}
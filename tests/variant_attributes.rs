@@ -0,0 +1,45 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum DatabaseError {
+    Anyhow(anyhow::Error),
+    Timeout,
+}
+
+#[derive(Debug)]
+struct ParseError;
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    /// Not marked with without_anyhow, only the doc comment mentions it.
+    #[allow(unused)]
+    Database(DatabaseError),
+
+    /// Parsing failed.
+    #[allow(unused)]
+    #[without_anyhow]
+    #[cfg_attr(test, allow(unused))]
+    Parse(ParseError),
+}
+
+#[test]
+fn mentioning_the_flag_does_not_set_it() {
+    let error = AppError::from(DatabaseError::Anyhow(anyhow::Error));
+    assert!(matches!(error, AppError::Anyhow(_)));
+
+    let error = AppError::from(DatabaseError::Timeout);
+    assert!(matches!(error, AppError::Database(DatabaseError::Timeout)));
+}
+
+#[test]
+fn flag_is_found_among_other_attributes() {
+    let error = AppError::from(ParseError);
+    assert!(matches!(error, AppError::Parse(ParseError)));
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    use std::fmt;
+
+    #[derive(Debug)]
+    pub struct Error(String);
+
+    impl<E: std::error::Error> From<E> for Error {
+        fn from(error: E) -> Self {
+            Error(error.to_string())
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+}
+
+// A status code that is no error itself, but can be turned into one
+#[derive(Debug, PartialEq)]
+struct StatusCode(u16);
+
+#[derive(Debug)]
+struct HttpError {
+    status: u16,
+}
+
+impl From<StatusCode> for HttpError {
+    fn from(code: StatusCode) -> Self {
+        HttpError { status: code.0 }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "request failed with status {}", self.status)
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Any(anyhow::Error),
+
+    #[via(HttpError)]
+    Status(StatusCode),
+}
+
+#[derive(Debug, ErrorEnum)]
+enum BoxedError {
+    Any(Box<anyhow::Error>),
+
+    #[via(HttpError)]
+    Status(StatusCode),
+}
+
+#[test]
+fn converts_in_two_steps() {
+    let error = AppError::from(StatusCode(503));
+    assert!(matches!(error, AppError::Any(inner) if inner.to_string() == "request failed with status 503"));
+}
+
+#[test]
+fn converts_into_boxed_aggregate() {
+    let error = BoxedError::from(StatusCode(404));
+    assert!(matches!(error, BoxedError::Any(inner) if inner.to_string() == "request failed with status 404"));
+}
+
+#[test]
+fn variant_is_still_constructible() {
+    let error = AppError::Status(StatusCode(200));
+    assert!(matches!(error, AppError::Status(StatusCode(200))));
+
+    let error = BoxedError::Status(StatusCode(200));
+    assert!(matches!(error, BoxedError::Status(StatusCode(200))));
+}
@@ -0,0 +1,51 @@
+use error_conversion_macro::ErrorEnum;
+
+mod errors {
+    use error_conversion_macro::ErrorEnum;
+
+    #[derive(Debug, PartialEq)]
+    pub struct DatabaseError;
+
+    #[derive(Debug, ErrorEnum)]
+    #[error_enum(no_aggregate, predicates, accessors, into_inner, kind, vis = "pub(crate)")]
+    pub enum AppError {
+        Database(DatabaseError),
+        NotFound,
+    }
+}
+
+mod inherited {
+    use error_conversion_macro::ErrorEnum;
+
+    #[derive(Debug, ErrorEnum)]
+    #[error_enum(no_aggregate, predicates)]
+    pub(crate) enum AppError {
+        NotFound,
+    }
+}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(no_aggregate, predicates, vis = "pub")]
+enum PrivateError {
+    NotFound,
+}
+
+#[test]
+fn restricted_methods_are_callable_within_the_crate() {
+    let error = errors::AppError::from(errors::DatabaseError);
+    assert!(error.is_database());
+    assert_eq!(error.as_database(), Some(&errors::DatabaseError));
+    assert_eq!(error.kind(), errors::AppErrorKind::Database);
+    assert_eq!(error.into_database().ok(), Some(errors::DatabaseError));
+    assert!(!errors::AppError::NotFound.is_database());
+}
+
+#[test]
+fn methods_inherit_enum_visibility() {
+    assert!(inherited::AppError::NotFound.is_not_found());
+}
+
+#[test]
+fn explicit_visibility() {
+    assert!(PrivateError::NotFound.is_not_found());
+}
@@ -0,0 +1,26 @@
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+}
+
+#[derive(Debug)]
+enum QueueError {
+    Full,
+}
+
+#[derive(Debug, ErrorEnum)]
+enum AppError {
+    Anyhow(anyhow::Error),
+
+    #[without_anyhow]
+    Queue(QueueError),
+}
+
+#[test]
+fn converts_into_the_variant() {
+    let error = AppError::from(QueueError::Full);
+    assert!(matches!(error, AppError::Queue(QueueError::Full)));
+    assert!(matches!(AppError::from(anyhow::Error), AppError::Anyhow(_)));
+}
@@ -0,0 +1,58 @@
+use std::error::Error as _;
+use std::fmt;
+
+use error_conversion_macro::ErrorEnum;
+
+mod anyhow {
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl AsRef<dyn std::error::Error> for Error {
+        fn as_ref(&self) -> &(dyn std::error::Error + 'static) {
+            &super::ParseError
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid syntax")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, ErrorEnum)]
+#[error_enum(std_error)]
+enum AppError {
+    #[error("unexpected")]
+    Anyhow(anyhow::Error),
+
+    #[wrap]
+    #[error("parse failed")]
+    Parse(ParseError),
+
+    #[without_anyhow]
+    Format(fmt::Error),
+}
+
+#[test]
+fn wrap_converts_without_extraction() {
+    let error = AppError::from(ParseError);
+    assert!(matches!(error, AppError::Parse(ParseError)));
+}
+
+#[test]
+fn wrapped_variant_keeps_its_source() {
+    let error = AppError::from(ParseError);
+    assert_eq!(error.source().unwrap().to_string(), "invalid syntax");
+}
+
+#[test]
+fn without_anyhow_has_no_source() {
+    let error = AppError::from(fmt::Error);
+    assert!(error.source().is_none());
+}